@@ -0,0 +1,166 @@
+use std::{collections::HashMap, fs, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pinocchio_template_sdk::example_program::example_program_id;
+use serde::Deserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+};
+
+/// Connection settings. Each flag overrides the selected profile, which overrides the Solana CLI
+/// config
+#[derive(Args)]
+pub struct CliConfigArgs {
+    /// Path to the Solana CLI config file
+    #[arg(long, global = true)]
+    pub config_file: Option<String>,
+    /// Profile to use from the profiles file
+    #[arg(long, short = 'p', global = true)]
+    pub profile: Option<String>,
+    /// Path to the profiles file, defaults to ~/.config/pinocchio-template/profiles.toml
+    #[arg(long, global = true)]
+    pub profiles_file: Option<String>,
+    /// The example program ID, for deployments under an ID other than `declare_id!`
+    #[arg(long, global = true)]
+    pub program_id: Option<Pubkey>,
+    /// RPC URL
+    #[arg(long, short = 'u', global = true)]
+    pub url: Option<String>,
+    /// Path to the signer keypair
+    #[arg(long, short = 'k', global = true)]
+    pub keypair: Option<String>,
+    /// Commitment level: processed, confirmed or finalized
+    #[arg(long, global = true)]
+    pub commitment: Option<String>,
+}
+
+/// A named cluster setup in the profiles file, e.g.
+///
+/// ```toml
+/// [profiles.devnet]
+/// url = "https://api.devnet.solana.com"
+/// program_id = "..."
+/// keypair = "~/.config/solana/devnet.json"
+/// commitment = "confirmed"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub url: Option<String>,
+    pub program_id: Option<String>,
+    pub keypair: Option<String>,
+    pub commitment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+impl Profile {
+    /// Loads `name` from the profiles file at `path`, or the default location
+    pub fn load(name: &str, path: Option<&str>) -> Result<Self> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => default_profiles_file()?,
+        };
+        let source = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("failed to read profiles file {}: {}", path.display(), e))?;
+        let mut file: ProfilesFile = toml::from_str(&source)
+            .map_err(|e| anyhow!("invalid profiles file {}: {}", path.display(), e))?;
+
+        file.profiles
+            .remove(name)
+            .ok_or_else(|| anyhow!("profile {} is not defined in {}", name, path.display()))
+    }
+}
+
+fn default_profiles_file() -> Result<PathBuf> {
+    let home =
+        std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set, pass --profiles-file"))?;
+
+    Ok(PathBuf::from(home).join(".config/pinocchio-template/profiles.toml"))
+}
+
+/// Expands a leading `~/`, which profiles written by hand commonly use
+fn expand_home(path: String) -> String {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest).display().to_string(),
+        _ => path,
+    }
+}
+
+pub struct CliConfig {
+    pub rpc_url: String,
+    pub keypair_path: String,
+    pub commitment: CommitmentConfig,
+    pub program_id: Pubkey,
+}
+
+impl CliConfig {
+    pub fn load(args: &CliConfigArgs) -> Result<Self> {
+        let solana_config = match args
+            .config_file
+            .as_ref()
+            .or(solana_cli_config::CONFIG_FILE.as_ref())
+        {
+            Some(path) if std::path::Path::new(path).exists() => {
+                solana_cli_config::Config::load(path)
+                    .map_err(|e| anyhow!("failed to load config file {}: {}", path, e))?
+            }
+            Some(path) if args.config_file.is_some() => {
+                return Err(anyhow!("config file {} does not exist", path));
+            }
+            _ => solana_cli_config::Config::default(),
+        };
+
+        let profile = match &args.profile {
+            Some(name) => Profile::load(name, args.profiles_file.as_deref())?,
+            None => Profile::default(),
+        };
+
+        let commitment = args
+            .commitment
+            .as_ref()
+            .or(profile.commitment.as_ref())
+            .unwrap_or(&solana_config.commitment);
+
+        let program_id = match (args.program_id, &profile.program_id) {
+            (Some(program_id), _) => program_id,
+            (None, Some(program_id)) => Pubkey::from_str(program_id)
+                .map_err(|e| anyhow!("invalid program_id in profile: {}", e))?,
+            (None, None) => example_program_id(),
+        };
+
+        Ok(Self {
+            rpc_url: args
+                .url
+                .clone()
+                .or(profile.url)
+                .unwrap_or(solana_config.json_rpc_url),
+            keypair_path: args
+                .keypair
+                .clone()
+                .or(profile.keypair.map(expand_home))
+                .unwrap_or(solana_config.keypair_path),
+            commitment: CommitmentConfig::from_str(commitment)
+                .map_err(|_| anyhow!("invalid commitment: {}", commitment))?,
+            program_id,
+        })
+    }
+
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.rpc_url.clone(), self.commitment)
+    }
+
+    pub fn keypair(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|e| anyhow!("failed to read keypair {}: {}", self.keypair_path, e))
+    }
+}
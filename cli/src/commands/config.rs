@@ -0,0 +1,268 @@
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use pinocchio_template_sdk::example_program::{
+    config_address_with_program_id, deserialize_config, initialize_config_ix_with_program_id,
+    update_config_ix_with_program_id,
+};
+use serde::Serialize;
+use solana_account_decoder::{encode_ui_account, UiAccountEncoding};
+use solana_rpc_client_api::response::RpcKeyedAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey, signature::Signer};
+
+use super::{
+    export::ExportedInstructionOutput, send_instructions, simulate::simulate_config_transaction,
+};
+use crate::{cli_config::CliConfig, output::OutputFormat};
+
+#[derive(Clone, Serialize)]
+pub struct ConfigOutput {
+    pub address: String,
+    pub base: String,
+    pub admin: String,
+    pub fees_bps: u64,
+    pub bump: u8,
+    pub lamports: u64,
+}
+
+impl ConfigOutput {
+    pub fn from_account(address: &Pubkey, account: &Account) -> Result<Self> {
+        let config = deserialize_config(&account.data)?;
+
+        Ok(Self {
+            address: address.to_string(),
+            base: Pubkey::new_from_array(*config.base()).to_string(),
+            admin: Pubkey::new_from_array(*config.admin()).to_string(),
+            fees_bps: config.fees_bps(),
+            bump: config.bump(),
+            lamports: account.lamports,
+        })
+    }
+
+    /// The decoded fields by name, in display order
+    pub fn fields(&self) -> [(&'static str, String); 5] {
+        [
+            ("base", self.base.clone()),
+            ("admin", self.admin.clone()),
+            ("fees_bps", self.fees_bps.to_string()),
+            ("bump", self.bump.to_string()),
+            ("lamports", self.lamports.to_string()),
+        ]
+    }
+}
+
+impl Display for ConfigOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Config: {}", self.address)?;
+        writeln!(f, "  Base: {}", self.base)?;
+        writeln!(f, "  Admin: {}", self.admin)?;
+        writeln!(f, "  Fees (bps): {}", self.fees_bps)?;
+        writeln!(f, "  Bump: {}", self.bump)?;
+        writeln!(f, "  Lamports: {}", self.lamports)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ConfigTransactionOutput {
+    pub action: &'static str,
+    pub config: String,
+    pub signature: String,
+}
+
+impl Display for ConfigTransactionOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} config {}", self.action, self.config)?;
+        writeln!(f, "Signature: {}", self.signature)
+    }
+}
+
+/// The admin an exported instruction is built for: `--admin` when given, which needs no keypair,
+/// otherwise the CLI keypair
+pub fn export_admin(
+    cli_config: &CliConfig,
+    export_ix: bool,
+    admin: Option<Pubkey>,
+) -> Result<Option<Pubkey>> {
+    if !export_ix {
+        return Ok(None);
+    }
+
+    match admin {
+        Some(admin) => Ok(Some(admin)),
+        None => Ok(Some(cli_config.keypair()?.pubkey())),
+    }
+}
+
+pub fn init_config(
+    cli_config: &CliConfig,
+    output: OutputFormat,
+    base: &Pubkey,
+    fees_bps: u64,
+    dry_run: bool,
+    export_admin: Option<Pubkey>,
+) -> Result<()> {
+    if let Some(admin) = export_admin {
+        let ix =
+            initialize_config_ix_with_program_id(&cli_config.program_id, base, &admin, fees_bps);
+        return output.print(&ExportedInstructionOutput::new(&ix)?);
+    }
+
+    let keypair = cli_config.keypair()?;
+    let ix = initialize_config_ix_with_program_id(
+        &cli_config.program_id,
+        base,
+        &keypair.pubkey(),
+        fees_bps,
+    );
+
+    if dry_run {
+        let (config, _) = config_address_with_program_id(&cli_config.program_id, base);
+        return output.print(&simulate_config_transaction(
+            cli_config,
+            &[ix],
+            &keypair,
+            &config,
+        )?);
+    }
+
+    let signature = send_instructions(cli_config, &[ix], &keypair)?;
+
+    output.print(&ConfigTransactionOutput {
+        action: "Initialized",
+        config: config_address_with_program_id(&cli_config.program_id, base)
+            .0
+            .to_string(),
+        signature: signature.to_string(),
+    })
+}
+
+pub fn update_config(
+    cli_config: &CliConfig,
+    output: OutputFormat,
+    base: &Pubkey,
+    new_admin: Option<Pubkey>,
+    new_fees_bps: Option<u64>,
+    dry_run: bool,
+    export_admin: Option<Pubkey>,
+) -> Result<()> {
+    if new_admin.is_none() && new_fees_bps.is_none() {
+        return Err(anyhow!(
+            "nothing to update, pass --new-admin and/or --new-fees-bps"
+        ));
+    }
+
+    if let Some(admin) = export_admin {
+        let ix = update_config_ix_with_program_id(
+            &cli_config.program_id,
+            base,
+            &admin,
+            new_admin,
+            new_fees_bps,
+        );
+        return output.print(&ExportedInstructionOutput::new(&ix)?);
+    }
+
+    let keypair = cli_config.keypair()?;
+    let ix = update_config_ix_with_program_id(
+        &cli_config.program_id,
+        base,
+        &keypair.pubkey(),
+        new_admin,
+        new_fees_bps,
+    );
+
+    if dry_run {
+        let (config, _) = config_address_with_program_id(&cli_config.program_id, base);
+        return output.print(&simulate_config_transaction(
+            cli_config,
+            &[ix],
+            &keypair,
+            &config,
+        )?);
+    }
+
+    let signature = send_instructions(cli_config, &[ix], &keypair)?;
+
+    output.print(&ConfigTransactionOutput {
+        action: "Updated",
+        config: config_address_with_program_id(&cli_config.program_id, base)
+            .0
+            .to_string(),
+        signature: signature.to_string(),
+    })
+}
+
+pub fn show_config(cli_config: &CliConfig, output: OutputFormat, base: &Pubkey) -> Result<()> {
+    let (address, _) = config_address_with_program_id(&cli_config.program_id, base);
+    let account = cli_config.rpc_client().get_account(&address)?;
+
+    output.print(&ConfigOutput::from_account(&address, &account)?)
+}
+
+#[derive(Serialize)]
+pub struct DumpedAccountOutput {
+    pub address: String,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct DumpConfigOutput {
+    pub accounts: Vec<DumpedAccountOutput>,
+}
+
+impl Display for DumpConfigOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for account in &self.accounts {
+            writeln!(f, "Wrote {} to {}", account.address, account.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the config for each of `bases` to `<outdir>/<config>.json`, in the format of
+/// `solana account --output json`, so tests can start from a cluster snapshot
+pub fn dump_config(
+    cli_config: &CliConfig,
+    output: OutputFormat,
+    bases: &[Pubkey],
+    outdir: &Path,
+) -> Result<()> {
+    let rpc_client = cli_config.rpc_client();
+    let mut accounts = Vec::with_capacity(bases.len());
+
+    for base in bases {
+        let (address, _) = config_address_with_program_id(&cli_config.program_id, base);
+        let account = rpc_client.get_account(&address).map_err(|e| {
+            anyhow!(
+                "failed to fetch config {} for base {}: {}",
+                address,
+                base,
+                e
+            )
+        })?;
+
+        let path = write_account_json(outdir, &address, &account)?;
+        accounts.push(DumpedAccountOutput {
+            address: address.to_string(),
+            path: path.display().to_string(),
+        });
+    }
+
+    output.print(&DumpConfigOutput { accounts })
+}
+
+fn write_account_json(outdir: &Path, address: &Pubkey, account: &Account) -> Result<PathBuf> {
+    let keyed_account = RpcKeyedAccount {
+        pubkey: address.to_string(),
+        account: encode_ui_account(address, account, UiAccountEncoding::Base64, None, None),
+    };
+
+    let path = outdir.join(format!("{}.json", address));
+    std::fs::write(&path, serde_json::to_string_pretty(&keyed_account)?)
+        .map_err(|e| anyhow!("failed to write {}: {}", path.display(), e))?;
+
+    Ok(path)
+}
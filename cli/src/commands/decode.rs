@@ -0,0 +1,233 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+};
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::{Subcommand, ValueEnum};
+use pinocchio_template_sdk::example_program::decoder::{
+    decode_account, decode_instruction_data, ExampleProgramAccount, ExampleProgramInstructionData,
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::output::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum DecodeCommand {
+    /// Decodes example program account data
+    Account {
+        /// The account data as base64 or 0x-prefixed hex, or a file holding either or the raw
+        /// bytes
+        input: String,
+        /// Detected from the input when not given
+        #[arg(long, value_enum)]
+        encoding: Option<ByteEncoding>,
+    },
+    /// Decodes example program instruction data
+    Ix {
+        /// The instruction data as base64 or 0x-prefixed hex
+        input: String,
+        /// Detected from the input when not given
+        #[arg(long, value_enum)]
+        encoding: Option<ByteEncoding>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ByteEncoding {
+    Base64,
+    Hex,
+}
+
+#[derive(Serialize)]
+pub struct DecodedField {
+    pub name: &'static str,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct DecodeOutput {
+    pub kind: &'static str,
+    pub name: &'static str,
+    pub len: usize,
+    pub fields: Vec<DecodedField>,
+}
+
+impl DecodeOutput {
+    fn new(kind: &'static str, name: &'static str, data: &[u8]) -> Self {
+        Self {
+            kind,
+            name,
+            len: data.len(),
+            fields: vec![DecodedField {
+                name: "discriminator",
+                value: data[0].to_string(),
+            }],
+        }
+    }
+
+    fn field(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.fields.push(DecodedField {
+            name,
+            value: value.to_string(),
+        });
+        self
+    }
+
+    fn account(data: &[u8]) -> Result<Self> {
+        let output = match decode_account(data)? {
+            ExampleProgramAccount::Config(config) => Self::new("account", "Config", data)
+                .field("bump", config.bump())
+                .field("base", Pubkey::new_from_array(*config.base()))
+                .field("admin", Pubkey::new_from_array(*config.admin()))
+                .field("fees_bps", config.fees_bps()),
+        };
+
+        Ok(output)
+    }
+
+    fn instruction(data: &[u8]) -> Result<Self> {
+        let decoded = decode_instruction_data(data)?;
+        let output = Self::new("instruction", decoded.name(), data);
+
+        Ok(match decoded {
+            ExampleProgramInstructionData::InitializeConfig(ix_data) => output
+                .field("config_bump", ix_data.config_bump)
+                .field("fees_bps", ix_data.fees_bps),
+            ExampleProgramInstructionData::UpdateConfig(ix_data) => output
+                .field("new_admin", format_option(ix_data.new_admin))
+                .field("new_fees_bps", format_option(ix_data.new_fees_bps)),
+        })
+    }
+}
+
+impl Display for DecodeOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {} ({} bytes)", self.name, self.kind, self.len)?;
+        for field in self.fields.iter() {
+            writeln!(f, "  {}: {}", field.name, field.value)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn process(output: OutputFormat, command: DecodeCommand) -> Result<()> {
+    let decoded = match command {
+        DecodeCommand::Account { input, encoding } => {
+            DecodeOutput::account(&read_account_input(&input, encoding)?)?
+        }
+        DecodeCommand::Ix { input, encoding } => {
+            DecodeOutput::instruction(&decode_bytes(&input, encoding)?)?
+        }
+    };
+
+    output.print(&decoded)
+}
+
+/// Account data may be given inline or as a file, which holds either encoded text or the raw
+/// bytes as written by `solana account --output-file`
+fn read_account_input(input: &str, encoding: Option<ByteEncoding>) -> Result<Vec<u8>> {
+    let path = Path::new(input);
+    if !path.is_file() {
+        return decode_bytes(input, encoding);
+    }
+
+    let bytes = fs::read(path).map_err(|e| anyhow!("failed to read {}: {}", input, e))?;
+    match (std::str::from_utf8(&bytes), encoding) {
+        (Ok(text), Some(encoding)) => decode_bytes(text, Some(encoding)),
+        (Ok(text), None) => decode_bytes(text, None).or(Ok(bytes)),
+        (Err(_), _) => Ok(bytes),
+    }
+}
+
+fn decode_bytes(input: &str, encoding: Option<ByteEncoding>) -> Result<Vec<u8>> {
+    let input = input.trim();
+    let encoding = encoding.unwrap_or_else(|| detect_encoding(input));
+
+    match encoding {
+        ByteEncoding::Base64 => BASE64
+            .decode(input)
+            .map_err(|e| anyhow!("invalid base64: {}", e)),
+        ByteEncoding::Hex => decode_hex(input),
+    }
+}
+
+/// Hex is recognized by its `0x` prefix, anything else is base64. A hex-looking string is not
+/// enough, base64 such as all-zero `AAAA` data only uses hex digits too.
+fn detect_encoding(input: &str) -> ByteEncoding {
+    if input.starts_with("0x") {
+        ByteEncoding::Hex
+    } else {
+        ByteEncoding::Base64
+    }
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    if let Some(index) = input.bytes().position(|byte| !byte.is_ascii_hexdigit()) {
+        return Err(anyhow!("invalid hex at offset {}", index));
+    }
+    if !input.len().is_multiple_of(2) {
+        return Err(anyhow!("invalid hex: odd number of digits"));
+    }
+
+    Ok(input
+        .as_bytes()
+        .chunks(2)
+        .map(|digits| (hex_digit(digits[0]) << 4) | hex_digit(digits[1]))
+        .collect())
+}
+
+/// The value of an ASCII hex digit, which `decode_hex` has already checked
+fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+fn format_option(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "none".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encoding() {
+        // 48 zero bytes in base64, made only of hex digits with an even length
+        let zeroes = "A".repeat(64);
+        assert_eq!(detect_encoding(&zeroes), ByteEncoding::Base64);
+        assert_eq!(decode_bytes(&zeroes, None).unwrap(), vec![0; 48]);
+
+        assert_eq!(detect_encoding("0x00ff"), ByteEncoding::Hex);
+        assert_eq!(decode_bytes("0x00ff", None).unwrap(), vec![0x00, 0xff]);
+        assert_eq!(
+            decode_bytes("00ff", Some(ByteEncoding::Hex)).unwrap(),
+            vec![0x00, 0xff]
+        );
+        assert_eq!(
+            decode_bytes("00ff", None).unwrap(),
+            BASE64.decode("00ff").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("0x0aFf").unwrap(), vec![0x0a, 0xff]);
+        assert_eq!(decode_hex("").unwrap(), Vec::<u8>::new());
+
+        assert!(decode_hex("0x0").is_err());
+        assert!(decode_hex("0xzz").is_err());
+        // `u8::from_str_radix` would accept a sign
+        assert!(decode_hex("+1").is_err());
+        // Multi-byte characters must not split a char boundary
+        assert!(decode_hex("0xa\u{e9}12").is_err());
+        assert!(decode_hex("\u{e9}").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use pinocchio_template_sdk::{deploy::hash_elf, rpc::get_program_data};
+use serde::Serialize;
+
+use crate::{cli_config::CliConfig, output::OutputFormat};
+
+/// The artifact `cargo build-sbf` writes for the example program
+pub const DEFAULT_PROGRAM_PATH: &str = "target/deploy/pinocchio_template_example_program.so";
+
+#[derive(Serialize)]
+pub struct VerifyDeployOutput {
+    pub program_id: String,
+    pub program_data: String,
+    pub upgrade_authority: Option<String>,
+    pub last_deploy_slot: u64,
+    pub deployed_hash: String,
+    pub deployed_len: usize,
+    pub local_path: String,
+    pub local_hash: String,
+    pub local_len: usize,
+    pub matches: bool,
+}
+
+impl Display for VerifyDeployOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Program: {}", self.program_id)?;
+        writeln!(f, "  Program data: {}", self.program_data)?;
+        writeln!(
+            f,
+            "  Upgrade authority: {}",
+            self.upgrade_authority
+                .as_deref()
+                .unwrap_or("none (immutable)")
+        )?;
+        writeln!(f, "  Last deploy slot: {}", self.last_deploy_slot)?;
+        writeln!(
+            f,
+            "  Deployed hash: {} ({} bytes)",
+            self.deployed_hash, self.deployed_len
+        )?;
+        writeln!(
+            f,
+            "  Local hash: {} ({} bytes, {})",
+            self.local_hash, self.local_len, self.local_path
+        )?;
+        writeln!(
+            f,
+            "{}",
+            if self.matches {
+                "Deployed program matches the local build"
+            } else {
+                "Deployed program does NOT match the local build"
+            }
+        )
+    }
+}
+
+/// Compares the deployed example program with a local build, failing if they differ
+pub fn verify_deploy(
+    cli_config: &CliConfig,
+    output: OutputFormat,
+    program_path: Option<PathBuf>,
+) -> Result<()> {
+    let program_path = program_path.unwrap_or_else(|| PathBuf::from(DEFAULT_PROGRAM_PATH));
+    let local = read_program(&program_path)?;
+    let local_hash = hash_elf(&local);
+
+    let program_id = cli_config.program_id;
+    let program_data = get_program_data(&cli_config.rpc_client(), &program_id)?;
+    let deployed_hash = program_data.hash();
+
+    let result = VerifyDeployOutput {
+        program_id: program_id.to_string(),
+        program_data: program_data.address.to_string(),
+        upgrade_authority: program_data.upgrade_authority.map(|key| key.to_string()),
+        last_deploy_slot: program_data.slot,
+        deployed_hash: deployed_hash.to_string(),
+        deployed_len: program_data.elf.len(),
+        local_path: program_path.display().to_string(),
+        local_hash: local_hash.to_string(),
+        local_len: local.len(),
+        matches: deployed_hash == local_hash,
+    };
+    output.print(&result)?;
+
+    if !result.matches {
+        return Err(anyhow!(
+            "deployed program {} does not match {}",
+            program_id,
+            program_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_program(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| {
+        anyhow!(
+            "failed to read {}: {}, build it with `cargo build-sbf` or pass --program-path",
+            path.display(),
+            e
+        )
+    })
+}
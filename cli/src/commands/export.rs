@@ -0,0 +1,72 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMetaOutput {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// An instruction in the formats multisig proposal UIs take, for admins that cannot sign with
+/// the CLI keypair
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedInstructionOutput {
+    pub program_id: String,
+    pub accounts: Vec<AccountMetaOutput>,
+    pub data_base58: String,
+    pub data_base64: String,
+    /// The whole bincode serialized instruction
+    pub instruction_base58: String,
+    pub instruction_base64: String,
+}
+
+impl ExportedInstructionOutput {
+    pub fn new(ix: &Instruction) -> Result<Self> {
+        let serialized = bincode::serialize(ix)
+            .map_err(|e| anyhow!("failed to serialize instruction: {}", e))?;
+
+        Ok(Self {
+            program_id: ix.program_id.to_string(),
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaOutput {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data_base58: bs58::encode(&ix.data).into_string(),
+            data_base64: BASE64.encode(&ix.data),
+            instruction_base58: bs58::encode(&serialized).into_string(),
+            instruction_base64: BASE64.encode(&serialized),
+        })
+    }
+}
+
+impl Display for ExportedInstructionOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Program ID: {}", self.program_id)?;
+        writeln!(f, "Accounts:")?;
+        for (index, meta) in self.accounts.iter().enumerate() {
+            let flags = match (meta.is_writable, meta.is_signer) {
+                (true, true) => "[writable, signer]",
+                (true, false) => "[writable]",
+                (false, true) => "[signer]",
+                (false, false) => "[]",
+            };
+            writeln!(f, "  {}. {} {}", index, flags, meta.pubkey)?;
+        }
+        writeln!(f, "Data (base58): {}", self.data_base58)?;
+        writeln!(f, "Data (base64): {}", self.data_base64)?;
+        writeln!(f, "Instruction (base58): {}", self.instruction_base58)?;
+        writeln!(f, "Instruction (base64): {}", self.instruction_base64)
+    }
+}
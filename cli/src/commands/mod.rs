@@ -0,0 +1,35 @@
+pub mod config;
+pub mod decode;
+pub mod deploy;
+pub mod export;
+pub mod offline;
+pub mod pda;
+pub mod simulate;
+
+use anyhow::Result;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+
+use crate::cli_config::CliConfig;
+
+/// Signs `instructions` with the CLI keypair as fee payer and sends them
+pub fn send_instructions(
+    cli_config: &CliConfig,
+    instructions: &[Instruction],
+    keypair: &Keypair,
+) -> Result<Signature> {
+    let rpc_client = cli_config.rpc_client();
+    let blockhash = rpc_client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&keypair.pubkey()),
+        &[keypair],
+        blockhash,
+    );
+
+    Ok(rpc_client.send_and_confirm_transaction(&tx)?)
+}
@@ -0,0 +1,174 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use pinocchio_template_sdk::{
+    example_program::{decoder::decode_instruction_data, update_config_ix_with_program_id},
+    offline::{
+        add_detached_signature, build_nonce_transaction, decode_transaction, encode_transaction,
+        format_detached_signature, missing_signer, parse_detached_signature, sign_detached,
+        summarize_transaction, TransactionEncoding, TransactionSummary,
+    },
+    rpc::get_nonce_hash,
+};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature};
+
+use crate::cli_config::CliConfig;
+
+#[derive(Subcommand)]
+pub enum OfflineCommand {
+    /// Builds an unsigned update-config transaction against a durable nonce
+    BuildUpdateConfig {
+        /// The base key the config is derived from
+        #[arg(long)]
+        base: Pubkey,
+        /// The current config admin
+        #[arg(long)]
+        admin: Pubkey,
+        #[arg(long)]
+        new_admin: Option<Pubkey>,
+        #[arg(long)]
+        new_fees_bps: Option<u64>,
+        /// Pays the transaction fee, defaults to the admin
+        #[arg(long)]
+        fee_payer: Option<Pubkey>,
+        /// The durable nonce account
+        #[arg(long)]
+        nonce: Pubkey,
+        /// The nonce authority, defaults to the admin
+        #[arg(long)]
+        nonce_authority: Option<Pubkey>,
+        /// The current durable nonce, fetched over RPC when not given
+        #[arg(long)]
+        nonce_hash: Option<Hash>,
+        #[arg(long, default_value = "base64")]
+        encoding: TransactionEncoding,
+    },
+    /// Prints what a serialized transaction does to stderr, then signs it with the CLI keypair and
+    /// prints the detached `<pubkey>=<signature>`
+    Sign {
+        transaction: String,
+        #[arg(long, default_value = "base64")]
+        encoding: TransactionEncoding,
+    },
+    /// Assembles a serialized transaction with its detached signatures and broadcasts it
+    Submit {
+        transaction: String,
+        /// A detached `<pubkey>=<signature>`, once per signer
+        #[arg(long = "signature", required = true)]
+        signatures: Vec<String>,
+        #[arg(long, default_value = "base64")]
+        encoding: TransactionEncoding,
+    },
+}
+
+pub fn process(cli_config: &CliConfig, command: OfflineCommand) -> Result<()> {
+    match command {
+        OfflineCommand::BuildUpdateConfig {
+            base,
+            admin,
+            new_admin,
+            new_fees_bps,
+            fee_payer,
+            nonce,
+            nonce_authority,
+            nonce_hash: hash,
+            encoding,
+        } => {
+            let hash = match hash {
+                Some(hash) => hash,
+                None => get_nonce_hash(&cli_config.rpc_client(), &nonce)?,
+            };
+
+            let ix = update_config_ix_with_program_id(
+                &cli_config.program_id,
+                &base,
+                &admin,
+                new_admin,
+                new_fees_bps,
+            );
+            let tx = build_nonce_transaction(
+                &[ix],
+                &fee_payer.unwrap_or(admin),
+                &nonce,
+                &nonce_authority.unwrap_or(admin),
+                hash,
+            );
+
+            println!("{}", encode_transaction(&tx, encoding)?);
+        }
+        OfflineCommand::Sign {
+            transaction,
+            encoding,
+        } => {
+            let tx = decode_transaction(&transaction, encoding)?;
+            let keypair = cli_config.keypair()?;
+
+            // The signer never sees the transaction in any other form, so it is shown before it
+            // is signed
+            eprint!(
+                "{}",
+                describe_transaction(cli_config, &summarize_transaction(&tx)?)
+            );
+
+            let (pubkey, signature) = sign_detached(&tx, &keypair)?;
+
+            println!("{}", format_detached_signature(&pubkey, &signature));
+        }
+        OfflineCommand::Submit {
+            transaction,
+            signatures,
+            encoding,
+        } => {
+            let mut tx = decode_transaction(&transaction, encoding)?;
+            for signature in signatures.iter() {
+                let (pubkey, signature): (Pubkey, Signature) = parse_detached_signature(signature)?;
+                add_detached_signature(&mut tx, &pubkey, &signature)?;
+            }
+
+            if let Some(pubkey) = missing_signer(&tx) {
+                return Err(anyhow!("missing signature for {}", pubkey));
+            }
+
+            let signature = cli_config.rpc_client().send_and_confirm_transaction(&tx)?;
+
+            println!("Signature: {}", signature);
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes a transaction for review, decoding the example program's instruction data
+fn describe_transaction(cli_config: &CliConfig, summary: &TransactionSummary) -> String {
+    let mut description = format!("Fee payer: {}\n", summary.fee_payer);
+    match summary.nonce {
+        Some((nonce, authority)) => {
+            description += &format!("Nonce account: {}\n", nonce);
+            description += &format!("Nonce authority: {}\n", authority);
+        }
+        None => description += &format!("Recent blockhash: {}\n", summary.recent_blockhash),
+    }
+
+    description += "Instructions:\n";
+    for (index, ix) in summary.instructions.iter().enumerate() {
+        description += &format!("  {}. Program {}\n", index, ix.program_id);
+        for meta in ix.accounts.iter() {
+            let flags = match (meta.is_writable, meta.is_signer) {
+                (true, true) => " (writable, signer)",
+                (true, false) => " (writable)",
+                (false, true) => " (signer)",
+                (false, false) => "",
+            };
+            description += &format!("     {}{}\n", meta.pubkey, flags);
+        }
+
+        let data = (ix.program_id == cli_config.program_id)
+            .then(|| decode_instruction_data(&ix.data).ok())
+            .flatten();
+        description += &match data {
+            Some(data) => format!("     {:?}\n", data),
+            None => format!("     {} bytes of data\n", ix.data.len()),
+        };
+    }
+
+    description
+}
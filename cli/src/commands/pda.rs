@@ -0,0 +1,241 @@
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use pinocchio_template_sdk::example_program::{
+    config_address_with_bump_and_program_id, config_address_with_program_id, deserialize_config,
+};
+use serde::Serialize;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair, Signer},
+};
+
+use crate::{cli_config::CliConfig, output::OutputFormat};
+
+#[derive(Subcommand)]
+pub enum PdaCommand {
+    /// Derives the config address for `base`
+    Config {
+        /// The base key the config is derived from
+        #[arg(long)]
+        base: Pubkey,
+    },
+    /// Finds which config `address` is, from the given bases or the account on chain
+    Verify {
+        address: Pubkey,
+        /// A candidate base, may be repeated. The account is fetched when none are given
+        #[arg(long = "base")]
+        bases: Vec<Pubkey>,
+    },
+}
+
+#[derive(Serialize)]
+pub struct PdaOutput {
+    pub address: String,
+    pub seeds: &'static str,
+    pub base: String,
+    pub bump: u8,
+}
+
+impl Display for PdaOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Address: {}", self.address)?;
+        writeln!(f, "  Seeds: {}", self.seeds)?;
+        writeln!(f, "  Base: {}", self.base)?;
+        writeln!(f, "  Bump: {}", self.bump)
+    }
+}
+
+impl PdaOutput {
+    fn config(address: &Pubkey, base: &Pubkey, bump: u8) -> Self {
+        Self {
+            address: address.to_string(),
+            seeds: "config",
+            base: base.to_string(),
+            bump,
+        }
+    }
+}
+
+pub fn process(cli_config: &CliConfig, output: OutputFormat, command: PdaCommand) -> Result<()> {
+    match command {
+        PdaCommand::Config { base } => {
+            let (address, bump) = config_address_with_program_id(&cli_config.program_id, &base);
+            output.print(&PdaOutput::config(&address, &base, bump))
+        }
+        PdaCommand::Verify { address, bases } => {
+            output.print(&verify(cli_config, &address, &bases)?)
+        }
+    }
+}
+
+fn verify(cli_config: &CliConfig, address: &Pubkey, bases: &[Pubkey]) -> Result<PdaOutput> {
+    if !bases.is_empty() {
+        return bases
+            .iter()
+            .find_map(|base| {
+                let (config, bump) = config_address_with_program_id(&cli_config.program_id, base);
+                config
+                    .eq(address)
+                    .then(|| PdaOutput::config(address, base, bump))
+            })
+            .ok_or_else(|| anyhow!("{} is not the config of any given base", address));
+    }
+
+    let account = cli_config.rpc_client().get_account(address).map_err(|e| {
+        anyhow!(
+            "failed to fetch {}, pass --base to check offline: {}",
+            address,
+            e
+        )
+    })?;
+    if account.owner.ne(&cli_config.program_id) {
+        return Err(anyhow!(
+            "{} is owned by {}, not the example program",
+            address,
+            account.owner
+        ));
+    }
+
+    // The stored base and bump are only trusted once they derive back to `address`
+    let config = deserialize_config(&account.data)?;
+    let base = Pubkey::new_from_array(*config.base());
+    if config_address_with_bump_and_program_id(&cli_config.program_id, &base, config.bump())?
+        .ne(address)
+    {
+        return Err(anyhow!(
+            "{} stores base {} and bump {}, which do not derive to it",
+            address,
+            base,
+            config.bump()
+        ));
+    }
+
+    Ok(PdaOutput::config(address, &base, config.bump()))
+}
+
+#[derive(Serialize)]
+pub struct GrindOutput {
+    pub base: String,
+    pub config: String,
+    pub keypair_path: String,
+}
+
+impl Display for GrindOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Base: {}", self.base)?;
+        writeln!(f, "  Config: {}", self.config)?;
+        writeln!(f, "  Keypair: {}", self.keypair_path)
+    }
+}
+
+#[derive(Serialize)]
+pub struct GrindResultsOutput {
+    pub attempts: u64,
+    pub found: Vec<GrindOutput>,
+}
+
+impl Display for GrindResultsOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for found in self.found.iter() {
+            write!(f, "{}", found)?;
+        }
+        writeln!(f, "Searched {} bases", self.attempts)
+    }
+}
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Generates base keypairs until `count` of them derive a config address starting with `prefix`
+pub fn grind_base(
+    output: OutputFormat,
+    program_id: Pubkey,
+    prefix: &str,
+    ignore_case: bool,
+    count: usize,
+    threads: usize,
+    outdir: PathBuf,
+) -> Result<()> {
+    if let Some(c) = prefix.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+        return Err(anyhow!("prefix contains {}, which is not base58", c));
+    }
+    if count == 0 || threads == 0 {
+        return Err(anyhow!("--count and --threads must be at least 1"));
+    }
+
+    let prefix = if ignore_case {
+        prefix.to_lowercase()
+    } else {
+        prefix.to_string()
+    };
+    let done = Arc::new(AtomicBool::new(false));
+    let attempts = Arc::new(AtomicU64::new(0));
+    let (sender, receiver) = mpsc::channel();
+
+    let workers: Vec<_> = (0..threads)
+        .map(|_| {
+            let (prefix, done, attempts, sender) = (
+                prefix.clone(),
+                done.clone(),
+                attempts.clone(),
+                sender.clone(),
+            );
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let base = Keypair::new();
+                    let (config, _) = config_address_with_program_id(&program_id, &base.pubkey());
+                    attempts.fetch_add(1, Ordering::Relaxed);
+
+                    let config_str = config.to_string();
+                    let matches = if ignore_case {
+                        config_str.to_lowercase().starts_with(&prefix)
+                    } else {
+                        config_str.starts_with(&prefix)
+                    };
+                    if matches && sender.send((base, config)).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut found = Vec::with_capacity(count);
+    for (base, config) in receiver.iter() {
+        let keypair_path = outdir.join(format!("{}.json", base.pubkey()));
+        write_keypair_file(&base, &keypair_path)
+            .map_err(|e| anyhow!("failed to write {}: {}", keypair_path.display(), e))?;
+
+        found.push(GrindOutput {
+            base: base.pubkey().to_string(),
+            config: config.to_string(),
+            keypair_path: keypair_path.display().to_string(),
+        });
+        if found.len() == count {
+            break;
+        }
+    }
+
+    done.store(true, Ordering::Relaxed);
+    drop(receiver);
+    for worker in workers {
+        worker
+            .join()
+            .map_err(|_| anyhow!("grind thread panicked"))?;
+    }
+
+    output.print(&GrindResultsOutput {
+        attempts: attempts.load(Ordering::Relaxed),
+        found,
+    })
+}
@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client_api::config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use super::config::ConfigOutput;
+use crate::{cli_config::CliConfig, output::ProgramErrorOutput};
+
+/// What a transaction would have done to a config, without sending it
+#[derive(Serialize)]
+pub struct DryRunOutput {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_error: Option<ProgramErrorOutput>,
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    pub config: ConfigDiff,
+}
+
+#[derive(Serialize)]
+pub struct ConfigDiff {
+    pub address: String,
+    pub before: Option<ConfigOutput>,
+    pub after: Option<ConfigOutput>,
+}
+
+impl Display for DryRunOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            None => writeln!(f, "Dry run succeeded, nothing was sent")?,
+            Some(error) => writeln!(f, "Dry run failed: {}", error)?,
+        }
+        if let Some(program_error) = &self.program_error {
+            writeln!(f, "  Program error: {}", program_error)?;
+        }
+        if let Some(units_consumed) = self.units_consumed {
+            writeln!(f, "Compute units consumed: {}", units_consumed)?;
+        }
+
+        writeln!(f, "Logs:")?;
+        for log in self.logs.iter() {
+            writeln!(f, "  {}", log)?;
+        }
+
+        write!(f, "{}", self.config)
+    }
+}
+
+impl Display for ConfigDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Config {}:", self.address)?;
+
+        match (&self.before, &self.after) {
+            (None, None) => writeln!(f, "  does not exist"),
+            (None, Some(after)) => {
+                writeln!(f, "  created")?;
+                for (name, value) in after.fields() {
+                    writeln!(f, "  {}: {}", name, value)?;
+                }
+                Ok(())
+            }
+            (Some(_), None) => writeln!(f, "  closed"),
+            (Some(before), Some(after)) => {
+                let mut changed = false;
+                for ((name, old), (_, new)) in before.fields().into_iter().zip(after.fields()) {
+                    if old != new {
+                        changed = true;
+                        writeln!(f, "  {}: {} -> {}", name, old, new)?;
+                    }
+                }
+                if !changed {
+                    writeln!(f, "  unchanged")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Simulates `instructions` signed by `keypair` and decodes `config` before and after
+pub fn simulate_config_transaction(
+    cli_config: &CliConfig,
+    instructions: &[Instruction],
+    keypair: &Keypair,
+    config: &Pubkey,
+) -> Result<DryRunOutput> {
+    let rpc_client = cli_config.rpc_client();
+
+    let before = rpc_client
+        .get_account_with_commitment(config, cli_config.commitment)?
+        .value
+        .and_then(|account| decode_config(cli_config, config, &account));
+
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&keypair.pubkey()),
+        &[keypair],
+        blockhash,
+    );
+
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            &tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(cli_config.commitment),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: vec![config.to_string()],
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+
+    let after = match result
+        .accounts
+        .as_ref()
+        .and_then(|accounts| accounts.first())
+    {
+        Some(Some(ui_account)) if result.err.is_none() => ui_account
+            .decode::<Account>()
+            .and_then(|account| decode_config(cli_config, config, &account)),
+        // Failed transactions leave the config untouched
+        _ => before.clone(),
+    };
+
+    let logs = result.logs.unwrap_or_default();
+    Ok(DryRunOutput {
+        success: result.err.is_none(),
+        error: result.err.as_ref().map(|err| err.to_string()),
+        program_error: result.err.as_ref().and_then(|err| {
+            ProgramErrorOutput::from_transaction_error(err, &logs, &cli_config.program_id)
+        }),
+        units_consumed: result.units_consumed,
+        logs,
+        config: ConfigDiff {
+            address: config.to_string(),
+            before,
+            after,
+        },
+    })
+}
+
+/// Decodes `account` as the config at `config`. Anyone can fund the config address before it is
+/// initialized, so an account the example program does not own, or cannot be decoded, is treated
+/// as no config rather than failing the dry run.
+fn decode_config(
+    cli_config: &CliConfig,
+    config: &Pubkey,
+    account: &Account,
+) -> Option<ConfigOutput> {
+    if account.owner != cli_config.program_id {
+        return None;
+    }
+
+    ConfigOutput::from_account(config, account).ok()
+}
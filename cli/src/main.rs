@@ -1,6 +1,244 @@
+mod cli_config;
+mod commands;
+mod output;
+
+use std::path::PathBuf;
+
+use anyhow::{Error, Result};
+use clap::{Parser, Subcommand};
+use cli_config::{CliConfig, CliConfigArgs};
+use commands::{decode::DecodeCommand, offline::OfflineCommand, pda::PdaCommand};
+use output::OutputFormat;
 use pinocchio_template_sdk::example_program;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Parser)]
+#[command(
+    author,
+    version,
+    about = "CLI for the pinocchio template example program"
+)]
+struct Args {
+    #[command(flatten)]
+    cli_config: CliConfigArgs,
+
+    /// Output format
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the example program ID
+    ProgramId,
+    /// Prints the example program IDL as JSON, with the address of the selected program ID
+    Idl,
+    /// Creates the config for `base`, with the CLI keypair or `--admin` as admin
+    InitConfig {
+        /// The base key the config is derived from
+        #[arg(long)]
+        base: Pubkey,
+        #[arg(long)]
+        fees_bps: u64,
+        /// Simulate the transaction and print what it would change, without sending it
+        #[arg(long, conflicts_with = "export_ix")]
+        dry_run: bool,
+        /// Print the instruction for a multisig proposal instead of sending it
+        #[arg(long)]
+        export_ix: bool,
+        /// The admin to export the instruction for, defaults to the CLI keypair
+        #[arg(long, requires = "export_ix")]
+        admin: Option<Pubkey>,
+    },
+    /// Updates the admin and/or fees of the config for `base`, signed by the current admin
+    UpdateConfig {
+        /// The base key the config is derived from
+        #[arg(long)]
+        base: Pubkey,
+        #[arg(long)]
+        new_admin: Option<Pubkey>,
+        #[arg(long)]
+        new_fees_bps: Option<u64>,
+        /// Simulate the transaction and print what it would change, without sending it
+        #[arg(long, conflicts_with = "export_ix")]
+        dry_run: bool,
+        /// Print the instruction for a multisig proposal instead of sending it
+        #[arg(long)]
+        export_ix: bool,
+        /// The admin to export the instruction for, defaults to the CLI keypair
+        #[arg(long, requires = "export_ix")]
+        admin: Option<Pubkey>,
+    },
+    /// Prints the config for `base`
+    ShowConfig {
+        /// The base key the config is derived from
+        #[arg(long)]
+        base: Pubkey,
+    },
+    /// Saves config accounts as `solana account --output json` files, to load into tests
+    DumpConfig {
+        /// The base key the config is derived from, may be repeated
+        #[arg(long = "base", required = true)]
+        bases: Vec<Pubkey>,
+        /// Where the accounts are written, as `<config>.json`
+        #[arg(long, default_value = ".")]
+        outdir: PathBuf,
+    },
+    /// Checks that the deployed example program matches a local build artifact
+    VerifyDeploy {
+        /// The local program binary, defaults to the `cargo build-sbf` output
+        #[arg(long)]
+        program_path: Option<PathBuf>,
+    },
+    /// Decodes raw example program account or instruction data
+    #[command(subcommand)]
+    Decode(DecodeCommand),
+    /// Derives and verifies example program addresses
+    #[command(subcommand)]
+    Pda(PdaCommand),
+    /// Generates base keypairs whose config address starts with a vanity prefix
+    GrindBase {
+        /// The base58 prefix the config address must start with
+        #[arg(long)]
+        prefix: String,
+        #[arg(long)]
+        ignore_case: bool,
+        /// How many bases to find
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+        /// Defaults to the number of CPUs
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Where the base keypairs are written, as `<base>.json`
+        #[arg(long, default_value = ".")]
+        outdir: PathBuf,
+    },
+    /// Builds, signs and submits transactions for air-gapped signers
+    #[command(subcommand)]
+    Offline(Box<OfflineCommand>),
+}
+
+#[derive(Serialize)]
+struct ProgramIdOutput {
+    program_id: String,
+}
+
+impl std::fmt::Display for ProgramIdOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Example Program ID: {}", self.program_id)
+    }
+}
+
+/// The IDL with the address of the resolved program ID, printed as pretty JSON for humans too
+#[derive(Serialize)]
+#[serde(transparent)]
+struct IdlOutput(serde_json::Value);
+
+impl std::fmt::Display for IdlOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self.0).map_err(|_| std::fmt::Error)?;
+        writeln!(f, "{}", json)
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let output = args.output;
+
+    let cli_config = match CliConfig::load(&args.cli_config) {
+        Ok(cli_config) => cli_config,
+        Err(error) => return exit_with_error(output, error, None),
+    };
+
+    match run(&cli_config, output, args.command) {
+        Err(error) => exit_with_error(output, error, Some(&cli_config.program_id)),
+        result => result,
+    }
+}
+
+/// Prints `error` as JSON and exits for JSON output, otherwise returns it. Program errors are only
+/// decoded for `program_id`, the resolved example program ID.
+fn exit_with_error(output: OutputFormat, error: Error, program_id: Option<&Pubkey>) -> Result<()> {
+    if output.is_json() {
+        output.print_error(&error, program_id);
+        std::process::exit(1);
+    }
+
+    Err(error)
+}
+
+fn run(cli_config: &CliConfig, output: OutputFormat, command: Command) -> Result<()> {
+    match command {
+        Command::ProgramId => output.print(&ProgramIdOutput {
+            program_id: cli_config.program_id.to_string(),
+        })?,
+        Command::Idl => {
+            let mut idl: serde_json::Value = serde_json::from_str(example_program::idl::IDL_JSON)?;
+            idl["address"] = cli_config.program_id.to_string().into();
+            output.print(&IdlOutput(idl))?
+        }
+        Command::InitConfig {
+            base,
+            fees_bps,
+            dry_run,
+            export_ix,
+            admin,
+        } => commands::config::init_config(
+            cli_config,
+            output,
+            &base,
+            fees_bps,
+            dry_run,
+            commands::config::export_admin(cli_config, export_ix, admin)?,
+        )?,
+        Command::UpdateConfig {
+            base,
+            new_admin,
+            new_fees_bps,
+            dry_run,
+            export_ix,
+            admin,
+        } => commands::config::update_config(
+            cli_config,
+            output,
+            &base,
+            new_admin,
+            new_fees_bps,
+            dry_run,
+            commands::config::export_admin(cli_config, export_ix, admin)?,
+        )?,
+        Command::ShowConfig { base } => commands::config::show_config(cli_config, output, &base)?,
+        Command::DumpConfig { bases, outdir } => {
+            commands::config::dump_config(cli_config, output, &bases, &outdir)?
+        }
+        Command::VerifyDeploy { program_path } => {
+            commands::deploy::verify_deploy(cli_config, output, program_path)?
+        }
+        Command::Decode(command) => commands::decode::process(output, command)?,
+        Command::Pda(command) => commands::pda::process(cli_config, output, command)?,
+        Command::GrindBase {
+            prefix,
+            ignore_case,
+            count,
+            threads,
+            outdir,
+        } => commands::pda::grind_base(
+            output,
+            cli_config.program_id,
+            &prefix,
+            ignore_case,
+            count,
+            threads.unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(1, |threads| threads.get())
+            }),
+            outdir,
+        )?,
+        Command::Offline(command) => commands::offline::process(cli_config, *command)?,
+    }
 
-fn main() {
-    let example_program_id = example_program::example_program_id();
-    println!("Example Program ID: {:?}", example_program_id);
+    Ok(())
 }
@@ -0,0 +1,188 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use pinocchio_template_sdk::example_program::errors::ExampleProgramError;
+use serde::Serialize;
+use solana_rpc_client_api::{
+    client_error::{Error as ClientError, ErrorKind as ClientErrorKind},
+    request::{RpcError, RpcResponseErrorData},
+    response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable
+    #[default]
+    Table,
+    /// Pretty printed JSON
+    Json,
+    /// Single line JSON
+    JsonCompact,
+}
+
+/// A command result that can be printed for humans or for automation
+pub trait CliOutput: Serialize + Display {}
+
+impl<T: Serialize + Display> CliOutput for T {}
+
+impl OutputFormat {
+    pub fn is_json(&self) -> bool {
+        matches!(self, Self::Json | Self::JsonCompact)
+    }
+
+    pub fn print(&self, output: &impl CliOutput) -> Result<()> {
+        match self {
+            Self::Table => print!("{}", output),
+            Self::Json => println!("{}", serde_json::to_string_pretty(output)?),
+            Self::JsonCompact => println!("{}", serde_json::to_string(output)?),
+        }
+
+        Ok(())
+    }
+
+    /// Prints `error` as JSON to stderr, decoding any error it carries from the example program
+    /// deployed at `example_program_id`
+    pub fn print_error(&self, error: &anyhow::Error, example_program_id: Option<&Pubkey>) {
+        let output = ErrorOutput {
+            error: ErrorDetails::new(error, example_program_id),
+        };
+
+        let json = match self {
+            Self::JsonCompact => serde_json::to_string(&output),
+            _ => serde_json::to_string_pretty(&output),
+        };
+        eprintln!("{}", json.expect("error output is serializable"));
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorOutput {
+    error: ErrorDetails,
+}
+
+#[derive(Serialize)]
+struct ErrorDetails {
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instruction_index: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instruction_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    program_error: Option<ProgramErrorOutput>,
+}
+
+#[derive(Serialize)]
+pub struct ProgramErrorOutput {
+    /// The program that failed, when the logs name it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    /// The `ExampleProgramError` name, only for codes the example program returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub code: u32,
+}
+
+impl ProgramErrorOutput {
+    /// Decodes a custom program error code into its `ExampleProgramError` name when the example
+    /// program, deployed at `example_program_id`, returned it. Other programs' codes, e.g. the
+    /// system program's, overlap with the example program's and are kept raw.
+    pub fn new(program_id: Option<Pubkey>, code: u32, example_program_id: &Pubkey) -> Self {
+        let name = program_id
+            .filter(|program_id| program_id == example_program_id)
+            .and_then(|_| ExampleProgramError::try_from(code).ok())
+            .map(|error| format!("{:?}", error));
+
+        Self {
+            program_id: program_id.map(|program_id| program_id.to_string()),
+            name,
+            code,
+        }
+    }
+
+    /// The custom error of a failed transaction, from the program that failed first in `logs`.
+    /// When the error came from a CPI, that is the invoked program rather than the failing
+    /// instruction's.
+    pub fn from_transaction_error(
+        error: &TransactionError,
+        logs: &[String],
+        example_program_id: &Pubkey,
+    ) -> Option<Self> {
+        let TransactionError::InstructionError(_, InstructionError::Custom(code)) = error else {
+            return None;
+        };
+
+        Some(Self::new(failed_program(logs), *code, example_program_id))
+    }
+}
+
+impl Display for ProgramErrorOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, self.code)?,
+            None => write!(f, "custom program error {}", self.code)?,
+        }
+        if let Some(program_id) = &self.program_id {
+            write!(f, " from {}", program_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The first program that failed, from its `Program <id> failed: <error>` log
+fn failed_program(logs: &[String]) -> Option<Pubkey> {
+    logs.iter().find_map(|log| {
+        let (program_id, _) = log.strip_prefix("Program ")?.split_once(" failed: ")?;
+        program_id.parse().ok()
+    })
+}
+
+impl ErrorDetails {
+    fn new(error: &anyhow::Error, example_program_id: Option<&Pubkey>) -> Self {
+        let mut details = Self {
+            message: error.to_string(),
+            instruction_index: None,
+            instruction_error: None,
+            program_error: None,
+        };
+
+        let Some(client_error) = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ClientError>())
+        else {
+            return details;
+        };
+
+        if let Some(transaction_error) = client_error.get_transaction_error() {
+            if let TransactionError::InstructionError(index, instruction_error) = &transaction_error
+            {
+                details.instruction_index = Some(*index);
+                details.instruction_error = Some(format!("{:?}", instruction_error));
+            }
+            // Preflight failures carry the simulation logs naming the failed program
+            let logs = match client_error.kind() {
+                ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                    data:
+                        RpcResponseErrorData::SendTransactionPreflightFailure(
+                            RpcSimulateTransactionResult {
+                                logs: Some(logs), ..
+                            },
+                        ),
+                    ..
+                }) => logs.as_slice(),
+                _ => &[],
+            };
+            details.program_error = example_program_id.and_then(|example_program_id| {
+                ProgramErrorOutput::from_transaction_error(
+                    &transaction_error,
+                    logs,
+                    example_program_id,
+                )
+            });
+        }
+
+        details
+    }
+}
@@ -0,0 +1,96 @@
+#![no_main]
+
+use std::collections::HashMap;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use pinocchio_template_fuzz::{
+    admin, base, initialize_config_data, read_config, update_config_data, ADMIN_COUNT,
+};
+use pinocchio_template_sdk::{
+    example_program::{config_address, initialize_config_ix, update_config_ix},
+    native::NativeContext,
+};
+use solana_program::pubkey::Pubkey;
+
+#[derive(Debug, Arbitrary)]
+enum Operation {
+    InitializeConfig {
+        base: u8,
+        admin: u8,
+        signed: bool,
+        fees_bps: u64,
+    },
+    UpdateConfig {
+        base: u8,
+        admin: u8,
+        signed: bool,
+        new_admin: Option<u8>,
+        new_fees_bps: Option<u64>,
+    },
+}
+
+// Sequences of init and update instructions, checked against a model of the configs: only the
+// signing admin changes a config and a base only ever gets one config
+fuzz_target!(|operations: Vec<Operation>| {
+    let mut context = NativeContext::new();
+    for index in 0..ADMIN_COUNT {
+        context.fund(&admin(index as u8), 100);
+    }
+    // Base -> (admin, fees_bps)
+    let mut model: HashMap<Pubkey, (Pubkey, u64)> = HashMap::new();
+
+    for operation in operations {
+        match operation {
+            Operation::InitializeConfig {
+                base: base_index,
+                admin: admin_index,
+                signed,
+                fees_bps,
+            } => {
+                let (base, admin) = (base(base_index), admin(admin_index));
+                let mut ix = initialize_config_ix(&base, &admin, fees_bps);
+                ix.data = initialize_config_data(config_address(&base).1, fees_bps);
+                ix.accounts[2].is_signer = signed;
+
+                let result = context.process_instruction(&ix);
+                let expected = signed && !model.contains_key(&base);
+                assert_eq!(result.is_ok(), expected, "{:?}", result);
+                if expected {
+                    model.insert(base, (admin, fees_bps));
+                }
+            }
+            Operation::UpdateConfig {
+                base: base_index,
+                admin: admin_index,
+                signed,
+                new_admin,
+                new_fees_bps,
+            } => {
+                let (base, admin) = (base(base_index), admin(admin_index));
+                let new_admin = new_admin.map(pinocchio_template_fuzz::admin);
+                let mut ix = update_config_ix(&base, &admin, new_admin, new_fees_bps);
+                ix.data = update_config_data(new_admin, new_fees_bps);
+                ix.accounts[1].is_signer = signed;
+
+                let result = context.process_instruction(&ix);
+                let current = model.get_mut(&base);
+                let expected = signed && current.as_ref().is_some_and(|(a, _)| *a == admin);
+                assert_eq!(result.is_ok(), expected, "{:?}", result);
+                if let (true, Some((current_admin, current_fees_bps))) = (expected, current) {
+                    *current_admin = new_admin.unwrap_or(*current_admin);
+                    *current_fees_bps = new_fees_bps.unwrap_or(*current_fees_bps);
+                }
+            }
+        }
+
+        for (base, (admin, fees_bps)) in &model {
+            let account = context.get_account(&config_address(base).0).unwrap();
+            let (_, config_base, config_admin, config_fees_bps) = read_config(account).unwrap();
+            assert_eq!(
+                (&config_base, &config_admin, &config_fees_bps),
+                (base, admin, fees_bps)
+            );
+        }
+    }
+});
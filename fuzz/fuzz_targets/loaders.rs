@@ -0,0 +1,66 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pinocchio_template_sdk::example_program::{
+    accounts::config::Config,
+    decoder::{
+        decode_account, decode_instruction_data, ExampleProgramAccount,
+        ExampleProgramInstructionData,
+    },
+    instructions::{
+        initialize_config::InitializeConfigIxData, update_config::UpdateConfigIxData,
+        ExampleProgramInstructions,
+    },
+    utils::{load_account, load_ix_data},
+};
+
+// The program's in-place loaders on arbitrary bytes, which must agree with the SDK decoders
+fuzz_target!(|data: &[u8]| {
+    // `Pod` types have an alignment of 1, so the fuzzer's bytes are loaded as they are
+    let config = load_account::<Config>(data);
+    match (config, decode_account(data)) {
+        (Ok(config), Ok(ExampleProgramAccount::Config(decoded))) => {
+            assert_eq!(config.bump(), decoded.bump());
+            assert_eq!(config.base(), decoded.base());
+            assert_eq!(config.admin(), decoded.admin());
+            assert_eq!(config.fees_bps(), decoded.fees_bps());
+        }
+        (Err(_), Err(_)) => {}
+        (config, decoded) => panic!("loader {:?} and decoder {:?} disagree", config, decoded),
+    }
+
+    let decoded = decode_instruction_data(data);
+    match data.first().map(ExampleProgramInstructions::try_from) {
+        Some(Ok(ExampleProgramInstructions::InitializeConfig)) => {
+            match (load_ix_data::<InitializeConfigIxData>(data), decoded) {
+                (Ok(ix_data), Ok(ExampleProgramInstructionData::InitializeConfig(decoded))) => {
+                    assert_eq!(ix_data.config_bump, decoded.config_bump);
+                    assert_eq!(ix_data.fees_bps.get(), decoded.fees_bps);
+                }
+                (Err(_), Err(_)) => {}
+                (ix_data, decoded) => {
+                    panic!("loader {:?} and decoder {:?} disagree", ix_data, decoded)
+                }
+            }
+        }
+        Some(Ok(ExampleProgramInstructions::UpdateConfig)) => {
+            match (UpdateConfigIxData::load(data), decoded) {
+                (Ok(ix_data), Ok(ExampleProgramInstructionData::UpdateConfig(decoded))) => {
+                    assert_eq!(
+                        ix_data.new_admin.get(),
+                        decoded.new_admin.map(|a| a.to_bytes())
+                    );
+                    assert_eq!(
+                        ix_data.new_fees_bps.get().map(u64::from),
+                        decoded.new_fees_bps
+                    );
+                }
+                (Err(_), Err(_)) => {}
+                (ix_data, decoded) => {
+                    panic!("loader {:?} and decoder {:?} disagree", ix_data, decoded)
+                }
+            }
+        }
+        _ => assert!(decoded.is_err()),
+    }
+});
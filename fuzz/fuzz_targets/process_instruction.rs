@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pinocchio_template_fuzz::{check_config_invariants, FuzzInstruction};
+use pinocchio_template_sdk::native::process_native_instruction;
+
+// Arbitrary accounts and instruction data through the dispatcher, which must never panic
+fuzz_target!(|input: FuzzInstruction| {
+    let (ix, before) = input.build();
+    let mut after = before.clone();
+
+    if process_native_instruction(&ix, &mut after).is_ok() {
+        check_config_invariants(&ix.accounts, &before, &after);
+    } else {
+        assert_eq!(before, after, "a failed instruction changed account state");
+    }
+});
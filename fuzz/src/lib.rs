@@ -0,0 +1,292 @@
+//! Structured inputs and invariants shared by the fuzz targets.
+//!
+//! Addresses are drawn from a small pool instead of being fully random, so generated
+//! instructions hit the config PDAs, the sysvars and duplicate accounts often enough to get past
+//! the first checks of each processor.
+
+use std::sync::LazyLock;
+
+use arbitrary::Arbitrary;
+use pinocchio_template_sdk::{
+    example_program::{
+        accounts::{config::Config, ExampleProgramDiscriminator},
+        config_address, example_program_id,
+        instructions::ExampleProgramInstructions,
+        utils::DataLen,
+    },
+    native::{NativeAccount, NativeContext},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+/// More accounts than any instruction takes, to cover the extra account cases
+pub const MAX_ACCOUNTS: usize = 8;
+
+pub const BASE_COUNT: usize = 2;
+pub const ADMIN_COUNT: usize = 3;
+
+/// Bases, their config PDAs, admins, then the rent sysvar, system program, program and a
+/// stranger
+pub static KEYS: LazyLock<Vec<Pubkey>> = LazyLock::new(|| {
+    let bases: Vec<Pubkey> = (0..BASE_COUNT)
+        .map(|i| Pubkey::new_from_array([i as u8 + 1; 32]))
+        .collect();
+    let admins = (0..ADMIN_COUNT).map(|i| Pubkey::new_from_array([i as u8 + 0x80; 32]));
+
+    let mut keys = bases.clone();
+    keys.extend(bases.iter().map(|base| config_address(base).0));
+    keys.extend(admins);
+    keys.extend([
+        sysvar::rent::id(),
+        system_program::id(),
+        example_program_id(),
+        Pubkey::new_from_array([0xff; 32]),
+    ]);
+    keys
+});
+
+/// Accounts whose state the runtime guarantees, so generating them would only find false
+/// positives
+static RUNTIME_ACCOUNTS: LazyLock<NativeContext> = LazyLock::new(NativeContext::new);
+
+pub fn key(index: u8) -> Pubkey {
+    KEYS[index as usize % KEYS.len()]
+}
+
+pub fn base(index: u8) -> Pubkey {
+    KEYS[index as usize % BASE_COUNT]
+}
+
+pub fn admin(index: u8) -> Pubkey {
+    KEYS[2 * BASE_COUNT + index as usize % ADMIN_COUNT]
+}
+
+#[derive(Debug, Arbitrary)]
+pub enum FuzzOwner {
+    System,
+    Program,
+    Key(u8),
+}
+
+#[derive(Debug, Arbitrary)]
+pub enum FuzzData {
+    Empty,
+    Config {
+        discriminator: u8,
+        bump: u8,
+        base: u8,
+        admin: u8,
+        fees_bps: u64,
+    },
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, Arbitrary)]
+pub struct FuzzAccount {
+    pub key: u8,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub lamports: u64,
+    pub owner: FuzzOwner,
+    pub data: FuzzData,
+}
+
+#[derive(Debug, Arbitrary)]
+pub enum FuzzIxData {
+    InitializeConfig {
+        bump: u8,
+        fees_bps: u64,
+    },
+    UpdateConfig {
+        new_admin: Option<u8>,
+        new_fees_bps: Option<u64>,
+    },
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, Arbitrary)]
+pub struct FuzzInstruction {
+    pub accounts: Vec<FuzzAccount>,
+    pub data: FuzzIxData,
+}
+
+impl FuzzAccount {
+    pub fn meta(&self) -> AccountMeta {
+        AccountMeta {
+            pubkey: key(self.key),
+            is_signer: self.is_signer,
+            is_writable: self.is_writable,
+        }
+    }
+
+    pub fn state(&self) -> NativeAccount {
+        if let Some(account) = RUNTIME_ACCOUNTS.get_account(&key(self.key)) {
+            return account.clone();
+        }
+
+        let owner = match self.owner {
+            FuzzOwner::System => system_program::id(),
+            FuzzOwner::Program => example_program_id(),
+            FuzzOwner::Key(index) => key(index),
+        };
+        let data = match &self.data {
+            FuzzData::Empty => vec![],
+            FuzzData::Config {
+                discriminator,
+                bump,
+                base: base_index,
+                admin: admin_index,
+                fees_bps,
+            } => config_data(
+                *discriminator,
+                *bump,
+                &base(*base_index),
+                &admin(*admin_index),
+                *fees_bps,
+            ),
+            FuzzData::Raw(data) => data.clone(),
+        };
+
+        NativeAccount::new(self.lamports, data, owner)
+    }
+}
+
+impl FuzzIxData {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::InitializeConfig { bump, fees_bps } => initialize_config_data(*bump, *fees_bps),
+            Self::UpdateConfig {
+                new_admin,
+                new_fees_bps,
+            } => update_config_data(new_admin.map(admin), *new_fees_bps),
+            Self::Raw(data) => data.clone(),
+        }
+    }
+}
+
+impl FuzzInstruction {
+    /// Builds the instruction and the account states it runs against. Duplicate accounts share
+    /// the state of their first occurrence, like they do on chain.
+    pub fn build(&self) -> (Instruction, Vec<NativeAccount>) {
+        let fuzz_accounts = &self.accounts[..self.accounts.len().min(MAX_ACCOUNTS)];
+        let metas: Vec<AccountMeta> = fuzz_accounts.iter().map(FuzzAccount::meta).collect();
+
+        let mut states: Vec<NativeAccount> = Vec::with_capacity(metas.len());
+        for (index, account) in fuzz_accounts.iter().enumerate() {
+            let first = metas.iter().position(|m| m.pubkey == metas[index].pubkey);
+            match first {
+                Some(first) if first < index => states.push(states[first].clone()),
+                _ => states.push(account.state()),
+            }
+        }
+
+        let ix = Instruction {
+            program_id: example_program_id(),
+            accounts: metas,
+            data: self.data.to_bytes(),
+        };
+
+        (ix, states)
+    }
+}
+
+// The encoders below write the `Pod` layouts by hand with zeroed reserved bytes, independently
+// of the SDK
+
+/// `Config` account data
+pub fn config_data(
+    discriminator: u8,
+    bump: u8,
+    base: &Pubkey,
+    admin: &Pubkey,
+    fees_bps: u64,
+) -> Vec<u8> {
+    let mut data = vec![discriminator, bump];
+    data.extend_from_slice(base.as_ref());
+    data.extend_from_slice(admin.as_ref());
+    data.resize(Config::LEN - 8, 0);
+    data.extend_from_slice(&fees_bps.to_le_bytes());
+    data
+}
+
+/// `InitializeConfigIxData`
+pub fn initialize_config_data(bump: u8, fees_bps: u64) -> Vec<u8> {
+    let mut data = vec![ExampleProgramInstructions::InitializeConfig as u8, bump];
+    data.resize(8, 0);
+    data.extend_from_slice(&fees_bps.to_le_bytes());
+    data
+}
+
+/// `UpdateConfigIxData`
+pub fn update_config_data(new_admin: Option<Pubkey>, new_fees_bps: Option<u64>) -> Vec<u8> {
+    let mut data = vec![ExampleProgramInstructions::UpdateConfig as u8];
+    data.push(new_admin.is_some() as u8);
+    data.extend_from_slice(new_admin.unwrap_or_default().as_ref());
+    data.resize(40, 0);
+    data.push(new_fees_bps.is_some() as u8);
+    data.resize(48, 0);
+    data.extend_from_slice(&new_fees_bps.unwrap_or_default().to_le_bytes());
+    data
+}
+
+/// The `(bump, base, admin, fees_bps)` of an initialized config
+pub fn read_config(account: &NativeAccount) -> Option<(u8, Pubkey, Pubkey, u64)> {
+    let data = &account.data;
+    if account.owner != example_program_id()
+        || data.len() != Config::LEN
+        || data[0] != ExampleProgramDiscriminator::Config as u8
+    {
+        return None;
+    }
+
+    Some((
+        data[1],
+        Pubkey::new_from_array(data[2..34].try_into().unwrap()),
+        Pubkey::new_from_array(data[34..66].try_into().unwrap()),
+        u64::from_le_bytes(data[72..80].try_into().unwrap()),
+    ))
+}
+
+/// Checks a successful instruction against the config invariants:
+/// - a config's `admin` and `fees_bps` only change when the current admin signed
+/// - an initialized config is never initialized again, its bump and base never change
+/// - a new config is only created by its signing admin
+pub fn check_config_invariants(
+    metas: &[AccountMeta],
+    before: &[NativeAccount],
+    after: &[NativeAccount],
+) {
+    let signed = |key: &Pubkey| metas.iter().any(|m| m.is_signer && &m.pubkey == key);
+
+    for ((meta, before), after) in metas.iter().zip(before).zip(after) {
+        match (read_config(before), read_config(after)) {
+            (Some((bump, base, admin, fees_bps)), Some(new)) => {
+                assert_eq!(
+                    (bump, base),
+                    (new.0, new.1),
+                    "config {} was initialized again",
+                    meta.pubkey
+                );
+                if (admin, fees_bps) != (new.2, new.3) {
+                    assert!(
+                        signed(&admin),
+                        "config {} changed without its admin {} signing",
+                        meta.pubkey,
+                        admin
+                    );
+                }
+            }
+            (Some(_), None) => panic!("config {} was closed or corrupted", meta.pubkey),
+            (None, Some((_, _, admin, _))) => assert!(
+                signed(&admin),
+                "config {} was created for admin {} without its signature",
+                meta.pubkey,
+                admin
+            ),
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use solana_program::{
+        clock::{Clock, DEFAULT_MS_PER_SLOT},
+        pubkey::Pubkey,
+    };
+    use solana_program_test::BanksClientError;
+
+    use crate::fixtures::fixture::TestBuilder;
+
+    fn assert_coherent(fixture: &TestBuilder, clock: &Clock) {
+        let epoch_schedule = &fixture.context.genesis_config().epoch_schedule;
+        assert_eq!(clock.epoch, epoch_schedule.get_epoch(clock.slot));
+        assert_eq!(
+            clock.leader_schedule_epoch,
+            epoch_schedule.get_leader_schedule_epoch(clock.slot)
+        );
+        assert!(clock.epoch_start_timestamp <= clock.unix_timestamp);
+    }
+
+    #[tokio::test]
+    async fn test_set_unix_timestamp() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        fixture
+            .set_unix_timestamp(before.unix_timestamp + 1_000)
+            .await
+            .unwrap();
+
+        let clock = fixture.get_clock().await.unwrap();
+        assert_eq!(
+            clock,
+            Clock {
+                unix_timestamp: before.unix_timestamp + 1_000,
+                ..before
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_unix_timestamp_before_epoch_start() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        fixture
+            .set_unix_timestamp(before.epoch_start_timestamp - 1)
+            .await
+            .unwrap();
+
+        let clock = fixture.get_clock().await.unwrap();
+        assert_eq!(clock.unix_timestamp, before.epoch_start_timestamp - 1);
+        assert_eq!(clock.epoch_start_timestamp, clock.unix_timestamp);
+        assert_coherent(&fixture, &clock);
+    }
+
+    #[tokio::test]
+    async fn test_advance_seconds() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        fixture.advance_seconds(60).await.unwrap();
+
+        let clock = fixture.get_clock().await.unwrap();
+        assert_eq!(clock.unix_timestamp, before.unix_timestamp + 60);
+        assert_eq!(clock.slot, before.slot + 60_000 / DEFAULT_MS_PER_SLOT);
+        assert_coherent(&fixture, &clock);
+
+        // The bank keeps processing transactions after the warp
+        fixture.transfer(&Pubkey::new_unique(), 1.0).await.unwrap();
+        assert_eq!(fixture.get_clock().await.unwrap(), clock);
+    }
+
+    #[tokio::test]
+    async fn test_advance_seconds_within_a_slot() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        fixture.advance_seconds(0).await.unwrap();
+
+        assert_eq!(fixture.get_clock().await.unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_warp_to_epoch() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+        let epoch = before.epoch + 2;
+
+        fixture.warp_to_epoch(epoch).await.unwrap();
+
+        let clock = fixture.get_clock().await.unwrap();
+        let epoch_schedule = &fixture.context.genesis_config().epoch_schedule;
+        assert_eq!(clock.slot, epoch_schedule.get_first_slot_in_epoch(epoch));
+        assert_eq!(clock.epoch, epoch);
+        assert_eq!(clock.epoch_start_timestamp, clock.unix_timestamp);
+        assert_eq!(
+            clock.unix_timestamp,
+            before.unix_timestamp
+                + ((clock.slot - before.slot) * DEFAULT_MS_PER_SLOT / 1_000) as i64
+        );
+        assert_coherent(&fixture, &clock);
+    }
+
+    #[tokio::test]
+    async fn test_warp_to_past_epoch_fails() {
+        let mut fixture = TestBuilder::new().await;
+        let epoch = fixture.get_clock().await.unwrap().epoch;
+
+        let result = fixture.warp_to_epoch(epoch).await;
+
+        assert!(matches!(result, Err(BanksClientError::ClientError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_clock() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+        let epoch_schedule = fixture.context.genesis_config().epoch_schedule.clone();
+        let slot = epoch_schedule.get_first_slot_in_epoch(before.epoch + 1) + 10;
+        let expected = Clock {
+            slot,
+            epoch_start_timestamp: 1_700_000_000,
+            epoch: epoch_schedule.get_epoch(slot),
+            leader_schedule_epoch: epoch_schedule.get_leader_schedule_epoch(slot),
+            unix_timestamp: 1_700_000_004,
+        };
+
+        fixture.set_clock(&expected).await.unwrap();
+
+        assert_eq!(fixture.get_clock().await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_set_clock_past_slot_fails() {
+        let mut fixture = TestBuilder::new().await;
+        fixture.warp_to_slot(100).await.unwrap();
+        let before = fixture.get_clock().await.unwrap();
+
+        let result = fixture
+            .set_clock(&Clock {
+                slot: before.slot - 1,
+                ..before.clone()
+            })
+            .await;
+
+        assert!(matches!(result, Err(BanksClientError::ClientError(_))));
+        assert_eq!(fixture.get_clock().await.unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_set_clock_wrong_epoch_fails() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        let result = fixture
+            .set_clock(&Clock {
+                epoch: before.epoch + 1,
+                ..before.clone()
+            })
+            .await;
+
+        assert!(matches!(result, Err(BanksClientError::ClientError(_))));
+        assert_eq!(fixture.get_clock().await.unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_set_clock_epoch_start_after_timestamp_fails() {
+        let mut fixture = TestBuilder::new().await;
+        let before = fixture.get_clock().await.unwrap();
+
+        let result = fixture
+            .set_clock(&Clock {
+                epoch_start_timestamp: before.unix_timestamp + 1,
+                ..before.clone()
+            })
+            .await;
+
+        assert!(matches!(result, Err(BanksClientError::ClientError(_))));
+    }
+}
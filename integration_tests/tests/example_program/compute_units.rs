@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{initialize_config_ix, update_config_ix};
+    use solana_program::{instruction::Instruction, pubkey::Pubkey};
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::fixtures::fixture::TestBuilder;
+
+    /// What the hand-written processors consumed before `instruction_accounts!` (af06b5b), which
+    /// the declarative checks must not exceed, measured from a `cargo build-sbf` of that commit.
+    /// The test fails until both are recorded.
+    const INITIALIZE_CONFIG_BASELINE_CU: Option<u64> = None;
+    const UPDATE_CONFIG_BASELINE_CU: Option<u64> = None;
+
+    /// Sends `ix` on its own and returns the compute units the SBF program consumed
+    async fn compute_units(fixture: &mut TestBuilder, ix: Instruction, signer: &Keypair) -> u64 {
+        let context = &mut fixture.context;
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, signer],
+            blockhash,
+        );
+
+        let result = context
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await
+            .unwrap();
+        result.result.unwrap();
+
+        result.metadata.unwrap().compute_units_consumed
+    }
+
+    fn assert_within_baseline(name: &str, consumed: u64, baseline: Option<u64>) {
+        let baseline = baseline.unwrap_or_else(|| {
+            panic!(
+                "{} consumed {} CU, but no baseline is recorded to compare against",
+                name, consumed
+            )
+        });
+        assert!(
+            consumed <= baseline,
+            "{} consumed {} CU, the baseline is {} CU",
+            name,
+            consumed,
+            baseline
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compute_units() {
+        let mut fixture = TestBuilder::new().await;
+        let admin = fixture.context.payer.insecure_clone();
+        let base = Pubkey::new_unique();
+
+        let initialize_config = compute_units(
+            &mut fixture,
+            initialize_config_ix(&base, &admin.pubkey(), 100),
+            &admin,
+        )
+        .await;
+        let update_config = compute_units(
+            &mut fixture,
+            update_config_ix(
+                &base,
+                &admin.pubkey(),
+                Some(Pubkey::new_unique()),
+                Some(200),
+            ),
+            &admin,
+        )
+        .await;
+
+        assert_within_baseline(
+            "InitializeConfig",
+            initialize_config,
+            INITIALIZE_CONFIG_BASELINE_CU,
+        );
+        assert_within_baseline("UpdateConfig", update_config, UPDATE_CONFIG_BASELINE_CU);
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{
+        accounts::config::Config,
+        config_address,
+        decoder::{
+            decode_account, decode_instruction, decode_instruction_data, ExampleProgramAccount,
+            ExampleProgramInstruction, ExampleProgramInstructionData, InitializeConfigAccounts,
+            InitializeConfigData, UpdateConfigAccounts, UpdateConfigData,
+        },
+        initialize_config_ix,
+        instructions::update_config::UpdateConfigIxData,
+        update_config_ix,
+        utils::DataLen,
+    };
+    use solana_program::{pubkey::Pubkey, system_program, sysvar};
+    use test_case::test_case;
+
+    #[test]
+    fn test_decode_initialize_config() {
+        let base = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let (config, config_bump) = config_address(&base);
+
+        let ix = initialize_config_ix(&base, &admin, 100);
+        let decoded = decode_instruction(&ix).unwrap();
+
+        assert_eq!(decoded.name(), "InitializeConfig");
+        assert_eq!(
+            decoded,
+            ExampleProgramInstruction::InitializeConfig {
+                accounts: InitializeConfigAccounts {
+                    config,
+                    base,
+                    admin,
+                    sysvar_rent: sysvar::rent::id(),
+                    system_program: system_program::id(),
+                },
+                data: InitializeConfigData {
+                    config_bump,
+                    fees_bps: 100,
+                },
+            }
+        );
+    }
+
+    #[test_case(None, None ; "no changes")]
+    #[test_case(Some(Pubkey::new_unique()), None ; "new admin")]
+    #[test_case(None, Some(250) ; "new fees")]
+    #[test_case(Some(Pubkey::new_unique()), Some(u64::MAX) ; "new admin and fees")]
+    fn test_decode_update_config(new_admin: Option<Pubkey>, new_fees_bps: Option<u64>) {
+        let base = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let (config, _) = config_address(&base);
+
+        let ix = update_config_ix(&base, &admin, new_admin, new_fees_bps);
+        let decoded = decode_instruction(&ix).unwrap();
+
+        assert_eq!(decoded.name(), "UpdateConfig");
+        assert_eq!(
+            decoded,
+            ExampleProgramInstruction::UpdateConfig {
+                accounts: UpdateConfigAccounts { config, admin },
+                data: UpdateConfigData {
+                    new_admin,
+                    new_fees_bps,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_wrong_program_fails() {
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), 100);
+        ix.program_id = Pubkey::new_unique();
+
+        assert!(decode_instruction(&ix).is_err());
+    }
+
+    #[test]
+    fn test_decode_malformed_data_fails() {
+        let mut ix = update_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), None, None);
+
+        ix.data.pop();
+        assert!(decode_instruction(&ix).is_err());
+
+        ix.data = vec![];
+        assert!(decode_instruction(&ix).is_err());
+
+        ix.data = vec![0xff];
+        assert!(decode_instruction(&ix).is_err());
+    }
+
+    #[test]
+    fn test_decode_missing_account_fails() {
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), 100);
+        ix.accounts.pop();
+
+        assert!(decode_instruction(&ix).is_err());
+    }
+
+    #[test]
+    fn test_decode_instruction_data() {
+        let ix = initialize_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), 100);
+        let decoded = decode_instruction_data(&ix.data).unwrap();
+
+        assert_eq!(decoded.name(), "InitializeConfig");
+        assert!(matches!(
+            decoded,
+            ExampleProgramInstructionData::InitializeConfig(InitializeConfigData {
+                fees_bps: 100,
+                ..
+            })
+        ));
+    }
+
+    #[test_case(1 ; "new admin")]
+    #[test_case(40 ; "new fees")]
+    fn test_decode_invalid_option_tag_fails(offset: usize) {
+        let mut ix = update_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), None, None);
+        ix.data[offset] = 2;
+
+        let error = decode_instruction_data(&ix.data).unwrap_err();
+        assert!(error.to_string().contains("option tags of 0 or 1"));
+    }
+
+    #[test]
+    fn test_decode_wrong_length_fails() {
+        let init = initialize_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), 100);
+        let update = update_config_ix(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            Some(100),
+        );
+
+        for data in [init.data, update.data] {
+            for len in 1..data.len() {
+                assert!(decode_instruction_data(&data[..len]).is_err());
+            }
+
+            let mut extended = data.clone();
+            extended.push(0);
+            assert!(decode_instruction_data(&extended).is_err());
+        }
+    }
+
+    /// The decoder accepts exactly the update data the program's own loader accepts
+    #[test]
+    fn test_decode_update_config_matches_program_loader() {
+        let ix = update_config_ix(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            Some(100),
+        );
+
+        for new_admin_tag in 0..=u8::MAX {
+            for new_fees_tag in [0, 1, 2, u8::MAX] {
+                let mut data = ix.data.clone();
+                data[1] = new_admin_tag;
+                data[40] = new_fees_tag;
+
+                assert_eq!(
+                    decode_instruction_data(&data).is_ok(),
+                    UpdateConfigIxData::load(&data).is_ok(),
+                    "tags {} and {}",
+                    new_admin_tag,
+                    new_fees_tag
+                );
+            }
+        }
+    }
+
+    fn config_data(base: &Pubkey, admin: &Pubkey, bump: u8, fees_bps: u64) -> Vec<u8> {
+        let mut data = vec![0; Config::LEN];
+        data[0] = 1;
+        data[1] = bump;
+        data[2..34].copy_from_slice(base.as_ref());
+        data[34..66].copy_from_slice(admin.as_ref());
+        data[72..80].copy_from_slice(&fees_bps.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_decode_config_account() {
+        let base = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+
+        let ExampleProgramAccount::Config(config) =
+            decode_account(&config_data(&base, &admin, 254, 100)).unwrap();
+
+        assert_eq!(config.bump(), 254);
+        assert_eq!(config.base(), &base.to_bytes());
+        assert_eq!(config.admin(), &admin.to_bytes());
+        assert_eq!(config.fees_bps(), 100);
+    }
+
+    #[test]
+    fn test_decode_malformed_account_fails() {
+        let mut data = config_data(&Pubkey::new_unique(), &Pubkey::new_unique(), 254, 100);
+
+        data.push(0);
+        let error = decode_account(&data).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Config expects {} bytes of account data, got {}",
+                Config::LEN,
+                Config::LEN + 1
+            )
+        );
+
+        data[0] = 0xff;
+        let error = decode_account(&data).unwrap_err();
+        assert_eq!(error.to_string(), "unknown account discriminator: 255");
+
+        assert!(decode_account(&[]).is_err());
+    }
+}
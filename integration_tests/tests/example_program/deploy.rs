@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::deploy::{hash_elf, parse_program_data, program_data_address};
+    use solana_sdk::{
+        account::Account,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        pubkey::Pubkey,
+    };
+    use test_case::test_case;
+
+    /// Builds a loader account the way the loader lays it out, with the programdata header
+    /// always taking its full size even when the authority is `None`
+    fn loader_account(state: &UpgradeableLoaderState, trailing: &[u8]) -> Account {
+        let mut data = bincode::serialize(state).unwrap();
+        if let UpgradeableLoaderState::ProgramData { .. } = state {
+            data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+        }
+        data.extend_from_slice(trailing);
+
+        Account {
+            lamports: 1,
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_program_data_address() {
+        let programdata_address = Pubkey::new_unique();
+        let account = loader_account(
+            &UpgradeableLoaderState::Program {
+                programdata_address,
+            },
+            &[],
+        );
+
+        assert_eq!(program_data_address(&account).unwrap(), programdata_address);
+    }
+
+    #[test]
+    fn test_program_data_address_wrong_owner_fails() {
+        let mut account = loader_account(
+            &UpgradeableLoaderState::Program {
+                programdata_address: Pubkey::new_unique(),
+            },
+            &[],
+        );
+        account.owner = Pubkey::new_unique();
+
+        assert!(program_data_address(&account).is_err());
+    }
+
+    #[test_case(Some(Pubkey::new_unique()) ; "upgradeable")]
+    #[test_case(None ; "immutable")]
+    fn test_parse_program_data(upgrade_authority: Option<Pubkey>) {
+        let address = Pubkey::new_unique();
+        let elf = [0x7f, b'E', b'L', b'F', 0, 1, 2];
+        let mut deployed = elf.to_vec();
+        deployed.extend_from_slice(&[0; 64]);
+
+        let account = loader_account(
+            &UpgradeableLoaderState::ProgramData {
+                slot: 42,
+                upgrade_authority_address: upgrade_authority,
+            },
+            &deployed,
+        );
+        let program_data = parse_program_data(&address, &account).unwrap();
+
+        assert_eq!(program_data.address, address);
+        assert_eq!(program_data.slot, 42);
+        assert_eq!(program_data.upgrade_authority, upgrade_authority);
+        assert_eq!(program_data.elf, elf);
+        assert_eq!(program_data.hash(), hash_elf(&elf));
+        assert_eq!(program_data.hash(), hash_elf(&deployed));
+    }
+
+    #[test]
+    fn test_parse_program_data_truncated_fails() {
+        let mut account = loader_account(
+            &UpgradeableLoaderState::ProgramData {
+                slot: 42,
+                upgrade_authority_address: None,
+            },
+            &[],
+        );
+        account.data.truncate(10);
+
+        assert!(parse_program_data(&Pubkey::new_unique(), &account).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{
+        config_address, initialize_config_ix, update_config_ix,
+    };
+    use solana_program::{instruction::Instruction, pubkey::Pubkey};
+    use solana_sdk::{
+        account::Account,
+        signature::{keypair_from_seed, Keypair, Signer},
+    };
+
+    use crate::fixtures::{fixture::TestBuilder, test_error::TestError};
+
+    /// Override with `DIFFERENTIAL_SCENARIOS` and `DIFFERENTIAL_SEED` to explore more
+    const DEFAULT_SCENARIOS: u64 = 8;
+    const DEFAULT_SEED: u64 = 0x5eed;
+    const STEPS: usize = 12;
+    const BASES: usize = 2;
+    const ADMINS: usize = 3;
+
+    /// xorshift64*, so a failing seed replays the same scenario
+    struct Rng(u64);
+
+    impl Rng {
+        fn new(seed: u64) -> Self {
+            Self(seed.max(1))
+        }
+
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn bytes(&mut self) -> [u8; 32] {
+            let mut bytes = [0; 32];
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next().to_le_bytes());
+            }
+            bytes
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn chance(&mut self, one_in: u64) -> bool {
+            self.next().is_multiple_of(one_in)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Step {
+        ix: Instruction,
+        /// Index into `Scenario::admins`, if the admin signs
+        signer: Option<usize>,
+    }
+
+    /// Init and update instructions over a few bases and admins, with random signers and
+    /// occasionally corrupted instruction data
+    struct Scenario {
+        bases: Vec<Pubkey>,
+        admins: Vec<Keypair>,
+        steps: Vec<Step>,
+    }
+
+    impl Scenario {
+        fn generate(seed: u64) -> Self {
+            let mut rng = Rng::new(seed);
+            let bases: Vec<Pubkey> = (0..BASES)
+                .map(|_| Pubkey::new_from_array(rng.bytes()))
+                .collect();
+            let admins: Vec<Keypair> = (0..ADMINS)
+                .map(|_| keypair_from_seed(&rng.bytes()).unwrap())
+                .collect();
+
+            let steps = (0..STEPS)
+                .map(|_| {
+                    let base = &bases[rng.below(BASES)];
+                    let admin = rng.below(ADMINS);
+                    let admin_key = admins[admin].pubkey();
+                    let fees_bps = rng.next() % 10_000;
+
+                    let (mut ix, admin_index) = if rng.chance(2) {
+                        (initialize_config_ix(base, &admin_key, fees_bps), 2)
+                    } else {
+                        let new_admin =
+                            (!rng.chance(3)).then(|| admins[rng.below(ADMINS)].pubkey());
+                        let new_fees_bps = (!rng.chance(3)).then_some(fees_bps);
+                        (
+                            update_config_ix(base, &admin_key, new_admin, new_fees_bps),
+                            1,
+                        )
+                    };
+
+                    let signed = !rng.chance(4);
+                    ix.accounts[admin_index].is_signer = signed;
+                    if rng.chance(5) {
+                        let index = rng.below(ix.data.len());
+                        ix.data[index] = rng.next() as u8;
+                    }
+
+                    Step {
+                        ix,
+                        signer: signed.then_some(admin),
+                    }
+                })
+                .collect();
+
+            Self {
+                bases,
+                admins,
+                steps,
+            }
+        }
+
+        /// The configs and admins, whose state both builds must agree on
+        fn tracked_accounts(&self) -> Vec<Pubkey> {
+            self.bases
+                .iter()
+                .map(|base| config_address(base).0)
+                .chain(self.admins.iter().map(Keypair::pubkey))
+                .collect()
+        }
+    }
+
+    struct Outcome {
+        results: Vec<Result<(), TestError>>,
+        accounts: Vec<Option<Account>>,
+    }
+
+    async fn run(mut fixture: TestBuilder, scenario: &Scenario) -> Outcome {
+        for admin in &scenario.admins {
+            fixture.transfer(&admin.pubkey(), 1.0).await.unwrap();
+        }
+
+        let mut results = Vec::with_capacity(scenario.steps.len());
+        for step in &scenario.steps {
+            let signers: Vec<&Keypair> = step
+                .signer
+                .map(|admin| &scenario.admins[admin])
+                .into_iter()
+                .collect();
+            results.push(
+                fixture
+                    .process_instructions(std::slice::from_ref(&step.ix), &signers)
+                    .await,
+            );
+        }
+
+        let mut accounts = Vec::new();
+        for address in scenario.tracked_accounts() {
+            accounts.push(
+                fixture
+                    .context
+                    .banks_client
+                    .get_account(address)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        Outcome { results, accounts }
+    }
+
+    fn env_or(name: &str, default: u64) -> u64 {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    }
+
+    #[tokio::test]
+    async fn test_differential_native_matches_sbf() {
+        let scenarios = env_or("DIFFERENTIAL_SCENARIOS", DEFAULT_SCENARIOS);
+        let first_seed = env_or("DIFFERENTIAL_SEED", DEFAULT_SEED);
+
+        for seed in first_seed..first_seed + scenarios {
+            let scenario = Scenario::generate(seed);
+
+            let sbf = run(TestBuilder::new().await, &scenario).await;
+            let native = run(TestBuilder::new_native().await, &scenario).await;
+
+            for (index, (sbf, native)) in sbf.results.iter().zip(&native.results).enumerate() {
+                assert_eq!(
+                    sbf, native,
+                    "seed {seed}: step {index} differs, SBF left, native right\n{:#?}",
+                    scenario.steps[index]
+                );
+            }
+            for (address, (sbf, native)) in scenario
+                .tracked_accounts()
+                .iter()
+                .zip(sbf.accounts.iter().zip(&native.accounts))
+            {
+                assert_eq!(
+                    sbf, native,
+                    "seed {seed}: account {address} differs, SBF left, native right"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{
+        example_program_id,
+        idl::{GENERATED_IDL_JSON, IDL_JSON},
+        initialize_config_ix, update_config_ix,
+    };
+    use serde_json::Value;
+    use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+    /// The committed IDL that `IDL_JSON` includes
+    const IDL_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../sdk/idl/example_program.json"
+    );
+
+    fn idl() -> Value {
+        serde_json::from_str(IDL_JSON).unwrap()
+    }
+
+    fn idl_instruction(name: &str) -> Value {
+        idl()["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|ix| ix["name"] == name)
+            .unwrap_or_else(|| panic!("{} missing from IDL", name))
+            .clone()
+    }
+
+    /// The instruction's args without its reserved `_padding` bytes
+    fn idl_arg_names(name: &str) -> Vec<String> {
+        idl_instruction(name)["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|arg| arg["name"].as_str().unwrap().to_string())
+            .filter(|name| !name.starts_with('_'))
+            .collect()
+    }
+
+    fn idl_arg_type(name: &str, arg: &str) -> Value {
+        idl_instruction(name)["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|idl_arg| idl_arg["name"] == arg)
+            .unwrap_or_else(|| panic!("{} has no arg {}", name, arg))["type"]
+            .clone()
+    }
+
+    fn assert_matches_builder(name: &str, ix: &Instruction) {
+        let idl_ix = idl_instruction(name);
+
+        assert_eq!(idl_ix["discriminator"], serde_json::json!([ix.data[0]]));
+
+        let idl_accounts = idl_ix["accounts"].as_array().unwrap();
+        assert_eq!(idl_accounts.len(), ix.accounts.len());
+        for (idl_account, meta) in idl_accounts.iter().zip(ix.accounts.iter()) {
+            assert_eq!(idl_account["writable"].as_bool().unwrap_or(false), meta.is_writable);
+            assert_eq!(idl_account["signer"].as_bool().unwrap_or(false), meta.is_signer);
+        }
+    }
+
+    /// `UPDATE_IDL=1 cargo test test_idl_is_up_to_date` rewrites the committed IDL
+    #[test]
+    fn test_idl_is_up_to_date() {
+        if IDL_JSON == GENERATED_IDL_JSON {
+            return;
+        }
+
+        if std::env::var_os("UPDATE_IDL").is_some() {
+            std::fs::write(IDL_PATH, GENERATED_IDL_JSON).unwrap();
+            return;
+        }
+        panic!(
+            "{} is out of date with the program sources, rerun with UPDATE_IDL=1 to update it",
+            IDL_PATH
+        );
+    }
+
+    #[test]
+    fn test_idl_address() {
+        assert_eq!(idl()["address"], example_program_id().to_string());
+    }
+
+    #[test]
+    fn test_idl_initialize_config_matches_builder() {
+        let ix = initialize_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), 100);
+        assert_matches_builder("initialize_config", &ix);
+
+        assert_eq!(
+            idl_arg_names("initialize_config"),
+            ["config_bump", "fees_bps"]
+        );
+    }
+
+    #[test]
+    fn test_idl_update_config_matches_builder() {
+        let ix = update_config_ix(&Pubkey::new_unique(), &Pubkey::new_unique(), None, None);
+        assert_matches_builder("update_config", &ix);
+
+        // Fixed-size `PodOption`s, not borsh options whose value is left out for `None`
+        assert_eq!(
+            idl_arg_type("update_config", "new_admin"),
+            pod_option("pubkey", 0)
+        );
+        assert_eq!(
+            idl_arg_type("update_config", "new_fees_bps"),
+            pod_option("u64", 7)
+        );
+    }
+
+    fn pod_option(ty: &str, pad: usize) -> Value {
+        serde_json::json!({
+            "defined": {
+                "name": "PodOption",
+                "generics": [
+                    { "kind": "type", "type": ty },
+                    { "kind": "const", "value": pad.to_string() },
+                ],
+            }
+        })
+    }
+
+    #[test]
+    fn test_idl_pod_option_is_fixed_size() {
+        let idl = idl();
+        let pod_option = idl["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|ty| ty["name"] == "PodOption")
+            .unwrap();
+
+        assert_eq!(pod_option["serialization"], "bytemuck");
+        assert_eq!(
+            pod_option["type"]["fields"],
+            serde_json::json!([
+                { "name": "tag", "type": "u8" },
+                { "name": "_padding", "type": { "array": ["u8", { "generic": "PAD" }] } },
+                { "name": "value", "type": { "generic": "T" } },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_idl_accounts_and_errors() {
+        let idl = idl();
+
+        assert_eq!(idl["accounts"][0]["name"], "Config");
+        assert_eq!(idl["accounts"][0]["discriminator"], serde_json::json!([1]));
+
+        let errors = idl["errors"].as_array().unwrap();
+        for (code, error) in errors.iter().enumerate() {
+            assert_eq!(error["code"], code);
+        }
+    }
+}
@@ -1 +1,13 @@
-mod tests;
\ No newline at end of file
+mod clock;
+mod compute_units;
+mod decoder;
+mod deploy;
+mod differential;
+mod idl;
+mod native;
+mod offline;
+mod pda;
+mod security;
+mod snapshot;
+mod tests;
+mod token;
\ No newline at end of file
@@ -0,0 +1,379 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::{
+        example_program::{
+            accounts::config::Config,
+            config_address, deserialize_config,
+            errors::ExampleProgramError,
+            example_program_id, initialize_config_ix,
+            pda::Pda,
+            update_config_ix,
+            utils::{create_account, DataLen},
+        },
+        native::{process_update_config, NativeAccount, NativeContext, NativeInput},
+    };
+    use solana_program::{
+        instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+        system_instruction::SystemError, system_program,
+    };
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use crate::{assert_program_error, fixtures::fixture::TestBuilder};
+
+    /// Account indexes, as declared by `InitializeConfigAccounts` and `UpdateConfigAccounts`
+    const INIT_ADMIN: usize = 2;
+    const INIT_SYSTEM_PROGRAM: usize = 4;
+    const UPDATE_ADMIN: usize = 1;
+
+    fn setup() -> (NativeContext, Pubkey) {
+        let mut context = NativeContext::new();
+        let admin = Pubkey::new_unique();
+        context.fund(&admin, 1);
+
+        (context, admin)
+    }
+
+    fn setup_config() -> (NativeContext, Pubkey, Pubkey) {
+        let (mut context, admin) = setup();
+        let base = Pubkey::new_unique();
+        context
+            .process_instruction(&initialize_config_ix(&base, &admin, 100))
+            .unwrap();
+
+        (context, admin, base)
+    }
+
+    fn get_config(context: &NativeContext, base: &Pubkey) -> Config {
+        let (config, _) = config_address(base);
+        let account = context.get_account(&config).unwrap();
+        assert_eq!(account.owner, example_program_id());
+
+        *deserialize_config(&account.data).unwrap()
+    }
+
+    #[test]
+    fn test_native_init_config_ok() {
+        let (mut context, admin) = setup();
+        let base = Pubkey::new_unique();
+        let (config, bump) = config_address(&base);
+        let admin_lamports = context.get_account(&admin).unwrap().lamports;
+
+        context
+            .process_instruction(&initialize_config_ix(&base, &admin, 100))
+            .unwrap();
+
+        let rent = Rent::default().minimum_balance(Config::LEN);
+        let account = context.get_account(&config).unwrap();
+        assert_eq!(account.lamports, rent);
+        assert_eq!(account.data.len(), Config::LEN);
+        assert_eq!(
+            context.get_account(&admin).unwrap().lamports,
+            admin_lamports - rent
+        );
+
+        let config = get_config(&context, &base);
+        assert_eq!(config.bump(), bump);
+        assert_eq!(config.base(), &base.to_bytes());
+        assert_eq!(config.admin(), &admin.to_bytes());
+        assert_eq!(config.fees_bps(), 100);
+    }
+
+    #[test]
+    fn test_native_update_config_ok() {
+        let (mut context, admin, base) = setup_config();
+        let new_admin = Pubkey::new_unique();
+
+        context
+            .process_instruction(&update_config_ix(&base, &admin, Some(new_admin), Some(250)))
+            .unwrap();
+
+        let config = get_config(&context, &base);
+        assert_eq!(config.admin(), &new_admin.to_bytes());
+        assert_eq!(config.fees_bps(), 250);
+    }
+
+    #[test]
+    fn test_native_init_config_twice_fails() {
+        let (mut context, admin, base) = setup_config();
+
+        assert_eq!(
+            context.process_instruction(&initialize_config_ix(&base, &admin, 100)),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+    }
+
+    #[test]
+    fn test_native_init_config_prefunded_fails() {
+        let (mut context, admin) = setup();
+        let base = Pubkey::new_unique();
+        let (config, _) = config_address(&base);
+        context.fund(&config, 1);
+
+        assert_eq!(
+            context.process_instruction(&initialize_config_ix(&base, &admin, 100)),
+            Err(ProgramError::Custom(
+                SystemError::AccountAlreadyInUse as u32
+            ))
+        );
+    }
+
+    #[test]
+    fn test_native_init_config_missing_admin_signature_fails() {
+        let (mut context, admin) = setup();
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin, 100);
+        ix.accounts[INIT_ADMIN].is_signer = false;
+
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_native_update_config_wrong_admin_fails() {
+        let (mut context, admin, base) = setup_config();
+        let impostor = Pubkey::new_unique();
+
+        assert_eq!(
+            context.process_instruction(&update_config_ix(&base, &impostor, None, Some(0))),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(get_config(&context, &base).admin(), &admin.to_bytes());
+    }
+
+    #[test]
+    fn test_native_update_config_missing_signature_keeps_state() {
+        let (mut context, admin, base) = setup_config();
+
+        let mut ix = update_config_ix(&base, &admin, None, Some(0));
+        ix.accounts[UPDATE_ADMIN].is_signer = false;
+
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(get_config(&context, &base).fees_bps(), 100);
+    }
+
+    #[test]
+    fn test_native_invalid_instruction_fails() {
+        let (mut context, admin, base) = setup_config();
+
+        let mut ix = update_config_ix(&base, &admin, None, None);
+        ix.data[0] = u8::MAX;
+
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::Custom(
+                ExampleProgramError::InvalidInstruction as u32
+            ))
+        );
+    }
+
+    #[test]
+    fn test_native_empty_instruction_data_fails() {
+        let (mut context, admin) = setup();
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin, 100);
+        ix.data.clear();
+
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::Custom(
+                ExampleProgramError::InvalidInstructionData as u32
+            ))
+        );
+    }
+
+    #[test]
+    fn test_native_update_config_invalid_option_tag_fails() {
+        let (mut context, admin, base) = setup_config();
+
+        for tag_offset in [1, 40] {
+            let mut ix = update_config_ix(&base, &admin, Some(Pubkey::new_unique()), Some(0));
+            ix.data[tag_offset] = u8::MAX;
+
+            assert_eq!(
+                context.process_instruction(&ix),
+                Err(ProgramError::Custom(
+                    ExampleProgramError::InvalidInstructionData as u32
+                ))
+            );
+        }
+        assert_eq!(get_config(&context, &base).admin(), &admin.to_bytes());
+    }
+
+    #[test]
+    fn test_native_init_config_checks_in_order() {
+        let (mut context, admin) = setup();
+
+        // The system program is checked first, then the config, the admin and the PDA
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin, 100);
+        ix.accounts[INIT_ADMIN].is_signer = false;
+        ix.accounts[INIT_SYSTEM_PROGRAM].pubkey = Pubkey::new_unique();
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin, 100);
+        ix.accounts[INIT_ADMIN].is_signer = false;
+        ix.data[1] = ix.data[1].wrapping_add(1);
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_native_update_config_checks_in_order() {
+        let (mut context, _, base) = setup_config();
+
+        // The accounts are counted before the data is read
+        let mut ix = update_config_ix(&base, &Pubkey::new_unique(), None, None);
+        ix.accounts.truncate(1);
+        ix.data[1] = u8::MAX;
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+
+        // The admin is checked before the config it must match
+        let mut ix = update_config_ix(&base, &Pubkey::new_unique(), None, None);
+        ix.accounts[UPDATE_ADMIN].is_signer = false;
+        assert_eq!(
+            context.process_instruction(&ix),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn test_native_processor_called_directly() {
+        let (context, admin, base) = setup_config();
+        let (config, _) = config_address(&base);
+
+        let ix = update_config_ix(&base, &admin, None, Some(42));
+        let accounts: Vec<NativeAccount> = ix
+            .accounts
+            .iter()
+            .map(|meta| context.get_account(&meta.pubkey).unwrap().clone())
+            .collect();
+
+        let mut input = NativeInput::new(&ix.program_id, &ix.accounts, &accounts, &ix.data);
+        input
+            .invoke(|program_id, account_infos, data| {
+                process_update_config(program_id, account_infos, data)
+            })
+            .unwrap();
+
+        let config_account = input.account(0);
+        assert_eq!(ix.accounts[0].pubkey, config);
+        assert_eq!(
+            deserialize_config(&config_account.data).unwrap().fees_bps(),
+            42
+        );
+    }
+
+    #[test]
+    fn test_native_create_account_checks_signer_seeds() {
+        let admin = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        let (config, bump) = config_address(&base);
+        let metas = [
+            AccountMeta::new(admin, true),
+            AccountMeta::new(config, false),
+        ];
+        let accounts = [
+            NativeAccount::new(10, vec![], system_program::id()),
+            NativeAccount::new(0, vec![], system_program::id()),
+        ];
+
+        // Like the runtime, the config can only be created with the seeds it is derived from
+        for (seeds_base, expected) in [
+            (
+                Pubkey::new_unique(),
+                Err(ProgramError::MissingRequiredSignature),
+            ),
+            (base, Ok(())),
+        ] {
+            let mut input = NativeInput::new(&example_program_id(), &metas, &accounts, &[]);
+            let result = input.invoke(|program_id, account_infos, _| {
+                let seeds_base = seeds_base.to_bytes();
+                let bump = [bump];
+                let seeds = Config::signer_seeds(&seeds_base, &bump);
+                create_account(
+                    &account_infos[0],
+                    &account_infos[1],
+                    0,
+                    program_id,
+                    1,
+                    &seeds,
+                )
+            });
+
+            assert_eq!(
+                result.map_err(|error| ProgramError::from(u64::from(error))),
+                expected
+            );
+            assert_eq!(input.account(1).lamports, expected.map_or(0, |_| 1));
+        }
+    }
+
+    #[test]
+    fn test_native_duplicate_accounts_share_state() {
+        let admin = Pubkey::new_unique();
+        let metas = [
+            AccountMeta::new_readonly(admin, false),
+            AccountMeta::new(admin, true),
+        ];
+        let accounts = [
+            NativeAccount::new(10, vec![], system_program::id()),
+            NativeAccount::new(10, vec![], system_program::id()),
+        ];
+
+        let mut input = NativeInput::new(&example_program_id(), &metas, &accounts, &[]);
+        input.invoke(|_, account_infos, _| {
+            assert_eq!(account_infos.len(), 2);
+            // Both entries point at one account carrying the union of the flags
+            assert!(account_infos[0].is_signer() && account_infos[0].is_writable());
+            *account_infos[1].try_borrow_mut_lamports().unwrap() += 5;
+            assert_eq!(account_infos[0].lamports(), 15);
+        });
+
+        assert_eq!(input.account(0), input.account(1));
+        assert_eq!(input.account(0).lamports, 15);
+    }
+
+    #[tokio::test]
+    async fn test_native_builtin_init_and_update() {
+        let mut fixture = TestBuilder::new_native().await;
+        let admin = Keypair::new();
+        let new_admin = Keypair::new();
+        fixture.transfer(&admin.pubkey(), 1.0).await.unwrap();
+        let base = Pubkey::new_unique();
+
+        let config = fixture.initialize_config(&base, &admin, 100).await.unwrap();
+        assert_eq!(config.admin, admin.pubkey());
+        assert_eq!(
+            config.lamports,
+            Rent::default().minimum_balance(Config::LEN)
+        );
+
+        let config = fixture
+            .update_config(&base, &admin, Some(new_admin.pubkey()), Some(250))
+            .await
+            .unwrap();
+        assert_eq!(config.admin, new_admin.pubkey());
+        assert_eq!(config.fees_bps, 250);
+
+        assert_program_error!(
+            fixture.initialize_config(&base, &admin, 100).await,
+            ProgramError::InvalidAccountOwner
+        );
+        assert_program_error!(
+            fixture.update_config(&base, &admin, None, Some(0)).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::{
+        example_program::{config_address, deserialize_config, initialize_config_ix, update_config_ix},
+        offline::{
+            add_detached_signature, build_nonce_transaction, decode_transaction,
+            encode_transaction, format_detached_signature, missing_signer, nonce_hash,
+            parse_detached_signature, sign_detached, summarize_transaction, TransactionEncoding,
+        },
+    };
+    use solana_program::{hash::Hash, pubkey::Pubkey, system_instruction};
+    use solana_sdk::{
+        commitment_config::CommitmentLevel,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::fixtures::fixture::TestBuilder;
+
+    async fn create_nonce_account(fixture: &mut TestBuilder, nonce: &Keypair, authority: &Pubkey) {
+        let rent = fixture.context.banks_client.get_rent().await.unwrap();
+        let lamports = rent.minimum_balance(solana_program::nonce::State::size());
+
+        let ixs = system_instruction::create_nonce_account(
+            &fixture.context.payer.pubkey(),
+            &nonce.pubkey(),
+            authority,
+            lamports,
+        );
+
+        let blockhash = fixture.context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&fixture.context.payer.pubkey()),
+            &[&fixture.context.payer, nonce],
+            blockhash,
+        );
+        fixture.context.banks_client.process_transaction_with_preflight_and_commitment(tx, CommitmentLevel::Processed).await.unwrap();
+
+        // The nonce can only be advanced once the blockhash it was created with is gone
+        fixture.warp_slot_incremental(1).await.unwrap();
+    }
+
+    async fn get_nonce_hash(fixture: &TestBuilder, nonce: &Pubkey) -> Hash {
+        let account = fixture.context.banks_client.get_account(*nonce).await.unwrap().unwrap();
+        nonce_hash(&account).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_offline_update_config_ok() {
+        let mut fixture = TestBuilder::new().await;
+
+        let admin = Keypair::new();
+        let base = Keypair::new();
+        let nonce = Keypair::new();
+        fixture.transfer(&admin.pubkey(), 1.0).await.unwrap();
+
+        let blockhash = fixture.context.banks_client.get_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_config_ix(&base.pubkey(), &admin.pubkey(), 100)],
+            Some(&fixture.context.payer.pubkey()),
+            &[&fixture.context.payer, &admin],
+            blockhash,
+        );
+        fixture.context.banks_client.process_transaction_with_preflight_and_commitment(tx, CommitmentLevel::Processed).await.unwrap();
+
+        create_nonce_account(&mut fixture, &nonce, &admin.pubkey()).await;
+        let hash = get_nonce_hash(&fixture, &nonce.pubkey()).await;
+
+        // Online: build the unsigned transaction
+        let new_admin = Keypair::new();
+        let ix = update_config_ix(&base.pubkey(), &admin.pubkey(), Some(new_admin.pubkey()), Some(200));
+        let tx = build_nonce_transaction(
+            &[ix],
+            &fixture.context.payer.pubkey(),
+            &nonce.pubkey(),
+            &admin.pubkey(),
+            hash,
+        );
+        let unsigned = encode_transaction(&tx, TransactionEncoding::Base64).unwrap();
+
+        // Offline: every signer signs its own copy
+        let admin_signature = {
+            let tx = decode_transaction(&unsigned, TransactionEncoding::Base64).unwrap();
+            let (pubkey, signature) = sign_detached(&tx, &admin).unwrap();
+            format_detached_signature(&pubkey, &signature)
+        };
+        let payer_signature = {
+            let tx = decode_transaction(&unsigned, TransactionEncoding::Base64).unwrap();
+            let (pubkey, signature) = sign_detached(&tx, &fixture.context.payer).unwrap();
+            format_detached_signature(&pubkey, &signature)
+        };
+
+        // Online: assemble and submit
+        let mut tx = decode_transaction(&unsigned, TransactionEncoding::Base64).unwrap();
+        assert!(missing_signer(&tx).is_some());
+        for signature in [admin_signature, payer_signature] {
+            let (pubkey, signature) = parse_detached_signature(&signature).unwrap();
+            add_detached_signature(&mut tx, &pubkey, &signature).unwrap();
+        }
+        assert!(missing_signer(&tx).is_none());
+
+        fixture.context.banks_client.process_transaction_with_preflight_and_commitment(tx, CommitmentLevel::Processed).await.unwrap();
+
+        let (config, _) = config_address(&base.pubkey());
+        let config_account_raw = fixture.context.banks_client.get_account(config).await.unwrap().unwrap();
+        let config_account = deserialize_config(&config_account_raw.data).unwrap();
+
+        assert_eq!(config_account.fees_bps(), 200);
+        assert_eq!(*config_account.admin(), new_admin.pubkey().to_bytes());
+        assert_ne!(get_nonce_hash(&fixture, &nonce.pubkey()).await, hash);
+    }
+
+    #[test]
+    fn test_detached_signatures() {
+        let admin = Keypair::new();
+        let fee_payer = Keypair::new();
+        let nonce = Pubkey::new_unique();
+
+        let ix = update_config_ix(&Pubkey::new_unique(), &admin.pubkey(), None, Some(200));
+        let tx = build_nonce_transaction(&[ix], &fee_payer.pubkey(), &nonce, &admin.pubkey(), Hash::new_unique());
+
+        for encoding in [TransactionEncoding::Base58, TransactionEncoding::Base64] {
+            let encoded = encode_transaction(&tx, encoding).unwrap();
+            assert_eq!(decode_transaction(&encoded, encoding).unwrap(), tx);
+        }
+
+        // Only required signers can sign
+        assert!(sign_detached(&tx, &Keypair::new()).is_err());
+
+        let mut signed = tx.clone();
+        let (pubkey, signature) = sign_detached(&tx, &admin).unwrap();
+
+        // Signatures are checked against the signer they claim to be from
+        assert!(add_detached_signature(&mut signed, &fee_payer.pubkey(), &signature).is_err());
+
+        add_detached_signature(&mut signed, &pubkey, &signature).unwrap();
+        assert_eq!(missing_signer(&signed), Some(fee_payer.pubkey()));
+
+        let (pubkey, signature) = sign_detached(&tx, &fee_payer).unwrap();
+        add_detached_signature(&mut signed, &pubkey, &signature).unwrap();
+        assert_eq!(missing_signer(&signed), None);
+        assert!(signed.verify().is_ok());
+    }
+
+    #[test]
+    fn test_summarize_transaction() {
+        let admin = Pubkey::new_unique();
+        let fee_payer = Pubkey::new_unique();
+        let nonce = Pubkey::new_unique();
+
+        let ix = update_config_ix(&Pubkey::new_unique(), &admin, None, Some(200));
+        let tx = build_nonce_transaction(std::slice::from_ref(&ix), &fee_payer, &nonce, &admin, Hash::new_unique());
+
+        let summary = summarize_transaction(&tx).unwrap();
+        assert_eq!(summary.fee_payer, fee_payer);
+        assert_eq!(summary.nonce, Some((nonce, admin)));
+        assert_eq!(summary.instructions, [ix]);
+    }
+
+    #[test]
+    fn test_malformed_transactions_are_rejected() {
+        let admin = Keypair::new();
+        let ix = update_config_ix(&Pubkey::new_unique(), &admin.pubkey(), None, Some(200));
+        let tx = build_nonce_transaction(&[ix], &admin.pubkey(), &Pubkey::new_unique(), &admin.pubkey(), Hash::new_unique());
+
+        // Fewer signature slots than required signers
+        let mut missing_slots = tx.clone();
+        missing_slots.signatures.clear();
+        let encoded = encode_transaction(&missing_slots, TransactionEncoding::Base64).unwrap();
+        assert!(decode_transaction(&encoded, TransactionEncoding::Base64).is_err());
+        assert_eq!(missing_signer(&missing_slots), Some(admin.pubkey()));
+        let (pubkey, signature) = sign_detached(&missing_slots, &admin).unwrap();
+        assert!(add_detached_signature(&mut missing_slots, &pubkey, &signature).is_err());
+
+        // More required signers than account keys
+        let mut too_many_signers = tx.clone();
+        too_many_signers.message.header.num_required_signatures = u8::MAX;
+        let encoded = encode_transaction(&too_many_signers, TransactionEncoding::Base64).unwrap();
+        assert!(decode_transaction(&encoded, TransactionEncoding::Base64).is_err());
+        assert!(summarize_transaction(&too_many_signers).is_err());
+        assert!(sign_detached(&too_many_signers, &Keypair::new()).is_err());
+
+        // An instruction account index past the account keys
+        let mut bad_index = tx;
+        bad_index.message.instructions[1].accounts[0] = u8::MAX;
+        let encoded = encode_transaction(&bad_index, TransactionEncoding::Base64).unwrap();
+        assert!(decode_transaction(&encoded, TransactionEncoding::Base64).is_err());
+        assert!(summarize_transaction(&bad_index).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{
+        accounts::config::Config,
+        config_address, config_address_with_bump, config_address_with_program_id,
+        decoder::{decode_instruction, decode_instruction_with_program_id},
+        example_program_id, initialize_config_ix_with_program_id,
+        pda::Pda,
+        update_config_ix_with_program_id,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_config_address_with_bump() {
+        let base = Pubkey::new_unique();
+        let (config, bump) = config_address(&base);
+
+        assert_eq!(config_address_with_bump(&base, bump).unwrap(), config);
+    }
+
+    #[test]
+    fn test_config_address_with_wrong_bump() {
+        let base = Pubkey::new_unique();
+        let (config, bump) = config_address(&base);
+
+        // A lower bump either lands on the curve or derives a different address
+        let derived = config_address_with_bump(&base, bump - 1);
+        assert!(derived.map_or(true, |address| address.ne(&config)));
+    }
+
+    #[test]
+    fn test_builders_with_program_id() {
+        let program_id = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let (config, _) = config_address_with_program_id(&program_id, &base);
+        assert_ne!(config, config_address(&base).0);
+
+        let ixs = [
+            initialize_config_ix_with_program_id(&program_id, &base, &admin, 100),
+            update_config_ix_with_program_id(&program_id, &base, &admin, None, Some(200)),
+        ];
+        for ix in ixs.iter() {
+            assert_eq!(ix.program_id, program_id);
+            assert_eq!(ix.accounts[0].pubkey, config);
+            assert!(decode_instruction_with_program_id(&program_id, ix).is_ok());
+            assert!(decode_instruction(ix).is_err());
+        }
+    }
+
+    #[test]
+    fn test_config_pda_seeds() {
+        let base = Pubkey::new_unique();
+        let (config, bump) = config_address(&base);
+
+        // The documented seeds, derived by the runtime's own implementation
+        let expected =
+            Pubkey::find_program_address(&[b"CONFIG", base.as_ref()], &example_program_id());
+        assert_eq!((config, bump), expected);
+
+        let base = base.to_bytes();
+        let bump = [bump];
+        let seeds = Config::seeds(&base, &bump);
+        assert_eq!(seeds, [&b"CONFIG"[..], &base, &bump]);
+        let signer_seeds = Config::signer_seeds(&base, &bump);
+        assert!(signer_seeds.iter().map(|seed| &seed[..]).eq(seeds));
+    }
+
+    #[test]
+    fn test_config_pda_create_with_bump() {
+        let base = Pubkey::new_unique();
+        let program_id = example_program_id();
+        let (config, bump) = Config::find(&base.to_bytes(), &program_id.to_bytes());
+
+        assert_eq!(config, config_address(&base).0.to_bytes());
+        assert_eq!(
+            Config::create_with_bump(&base.to_bytes(), bump, &program_id.to_bytes()),
+            Ok(config)
+        );
+    }
+}
@@ -0,0 +1,437 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::example_program::{
+        config_address, config_address_with_bump, errors::ExampleProgramError,
+        example_program_id, initialize_config_ix, update_config_ix,
+    };
+    use solana_program::{
+        instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+        system_instruction::SystemError, system_program,
+    };
+    use solana_sdk::{
+        account::{Account, AccountSharedData},
+        signature::{Keypair, Signer},
+    };
+
+    use crate::{
+        assert_program_error,
+        fixtures::{fixture::TestBuilder, test_error::TestError},
+    };
+
+    /// Account indexes, as declared by `InitializeConfigAccounts` and `UpdateConfigAccounts`
+    const CONFIG: usize = 0;
+    const INIT_ADMIN: usize = 2;
+    const INIT_SYSTEM_PROGRAM: usize = 4;
+    const UPDATE_ADMIN: usize = 1;
+
+    /// A funded admin that is not the fee payer, so dropping its signature is not masked by the
+    /// payer signing
+    async fn setup() -> (TestBuilder, Keypair) {
+        let mut fixture = TestBuilder::new().await;
+        let admin = Keypair::new();
+        fixture.transfer(&admin.pubkey(), 1.0).await.unwrap();
+
+        (fixture, admin)
+    }
+
+    async fn setup_config() -> (TestBuilder, Keypair, Pubkey) {
+        let (mut fixture, admin) = setup().await;
+        let base = Pubkey::new_unique();
+        fixture.initialize_config(&base, &admin, 100).await.unwrap();
+
+        (fixture, admin, base)
+    }
+
+    /// A base whose config bump minus one also derives an address off the curve, so a wrong bump
+    /// reliably fails the PDA comparison rather than the derivation itself
+    fn base_with_wrong_bump() -> (Pubkey, u8) {
+        loop {
+            let base = Pubkey::new_unique();
+            let (_, bump) = config_address(&base);
+            if let Some(wrong_bump) = bump.checked_sub(1) {
+                if config_address_with_bump(&base, wrong_bump).is_ok() {
+                    return (base, wrong_bump);
+                }
+            }
+        }
+    }
+
+    async fn get_account(fixture: &TestBuilder, address: &Pubkey) -> Account {
+        fixture.context.banks_client.get_account(*address).await.unwrap().unwrap()
+    }
+
+    fn set_account(fixture: &mut TestBuilder, address: &Pubkey, account: Account) {
+        fixture.context.set_account(address, &AccountSharedData::from(account));
+    }
+
+    async fn process(
+        fixture: &mut TestBuilder,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), TestError> {
+        fixture.process_instructions(&[ix], signers).await
+    }
+
+    // ----------------------- INITIALIZE CONFIG -----------------------
+
+    #[tokio::test]
+    async fn test_init_config_wrong_system_program_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.accounts[INIT_SYSTEM_PROGRAM].pubkey = Pubkey::new_unique();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::IncorrectProgramId
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_wrong_owner_fails() {
+        let (mut fixture, admin) = setup().await;
+        let base = Pubkey::new_unique();
+        let (config, _) = config_address(&base);
+
+        set_account(
+            &mut fixture,
+            &config,
+            Account {
+                lamports: 1_000_000,
+                owner: Pubkey::new_unique(),
+                ..Account::default()
+            },
+        );
+
+        assert_program_error!(
+            fixture.initialize_config(&base, &admin, 100).await,
+            ProgramError::InvalidAccountOwner
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_twice_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        // The initialized config is owned by the program, not the system program
+        assert_program_error!(
+            fixture.initialize_config(&base, &admin, 100).await,
+            ProgramError::InvalidAccountOwner
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_prefunded_fails() {
+        let (mut fixture, admin) = setup().await;
+        let base = Pubkey::new_unique();
+        let (config, _) = config_address(&base);
+
+        // Anyone can send lamports to the config address before it is created
+        fixture.transfer(&config, 0.01).await.unwrap();
+
+        assert_program_error!(
+            fixture.initialize_config(&base, &admin, 100).await,
+            system_program::id(),
+            ProgramError::Custom(SystemError::AccountAlreadyInUse as u32)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_with_data_fails() {
+        let (mut fixture, admin) = setup().await;
+        let base = Pubkey::new_unique();
+        let (config, _) = config_address(&base);
+
+        set_account(
+            &mut fixture,
+            &config,
+            Account {
+                lamports: 1_000_000,
+                data: vec![0; 8],
+                owner: solana_program::system_program::id(),
+                ..Account::default()
+            },
+        );
+
+        assert_program_error!(
+            fixture.initialize_config(&base, &admin, 100).await,
+            ProgramError::AccountAlreadyInitialized
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_readonly_config_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.accounts[CONFIG].is_writable = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_missing_admin_signature_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.accounts[INIT_ADMIN].is_signer = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[]).await,
+            ProgramError::MissingRequiredSignature
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_readonly_admin_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.accounts[INIT_ADMIN].is_writable = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_wrong_pda_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.accounts[CONFIG].pubkey = Pubkey::new_unique();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_wrong_bump_fails() {
+        let (mut fixture, admin) = setup().await;
+        let (base, wrong_bump) = base_with_wrong_bump();
+
+        // The bump directly follows the discriminator
+        let mut ix = initialize_config_ix(&base, &admin.pubkey(), 100);
+        ix.data[1] = wrong_bump;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_config_truncated_data_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.data.pop();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ExampleProgramError::InvalidInstructionData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_instruction_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.data[0] = 0xff;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ExampleProgramError::InvalidInstruction
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_instruction_data_fails() {
+        let (mut fixture, admin) = setup().await;
+
+        let mut ix = initialize_config_ix(&Pubkey::new_unique(), &admin.pubkey(), 100);
+        ix.data.clear();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ExampleProgramError::InvalidInstructionData
+        );
+    }
+
+    // ----------------------- UPDATE CONFIG -----------------------
+
+    #[tokio::test]
+    async fn test_update_config_non_admin_fails() {
+        let (mut fixture, _, base) = setup_config().await;
+
+        let attacker = Keypair::new();
+        fixture.transfer(&attacker.pubkey(), 1.0).await.unwrap();
+
+        assert_program_error!(
+            fixture
+                .update_config(&base, &attacker, Some(attacker.pubkey()), None)
+                .await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_missing_admin_signature_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        ix.accounts[UPDATE_ADMIN].is_signer = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[]).await,
+            ProgramError::MissingRequiredSignature
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_readonly_admin_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        ix.accounts[UPDATE_ADMIN].is_writable = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_readonly_config_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        ix.accounts[CONFIG].is_writable = false;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_wrong_owner_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+        let (config, _) = config_address(&base);
+
+        let mut account = get_account(&fixture, &config).await;
+        account.owner = Pubkey::new_unique();
+        set_account(&mut fixture, &config, account);
+
+        assert_program_error!(
+            fixture.update_config(&base, &admin, None, Some(200)).await,
+            ProgramError::InvalidAccountOwner
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_wrong_pda_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+        let (config, _) = config_address(&base);
+
+        // A copy of a valid config at an address it does not derive to
+        let fake_config = Pubkey::new_unique();
+        let account = get_account(&fixture, &config).await;
+        set_account(&mut fixture, &fake_config, account);
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), Some(admin.pubkey()), None);
+        ix.accounts[CONFIG].pubkey = fake_config;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_wrong_bump_fails() {
+        let (mut fixture, admin) = setup().await;
+        let (base, wrong_bump) = base_with_wrong_bump();
+        let config = fixture.initialize_config(&base, &admin, 100).await.unwrap();
+
+        // The stored bump directly follows the discriminator
+        let mut account = get_account(&fixture, &config.address).await;
+        account.data[1] = wrong_bump;
+        set_account(&mut fixture, &config.address, account);
+
+        assert_program_error!(
+            fixture.update_config(&base, &admin, None, Some(200)).await,
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_uninitialized_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+        let (config, _) = config_address(&base);
+
+        let mut account = get_account(&fixture, &config).await;
+        account.data.fill(0);
+        set_account(&mut fixture, &config, account);
+
+        assert_program_error!(
+            fixture.update_config(&base, &admin, None, Some(200)).await,
+            ProgramError::UninitializedAccount
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_truncated_data_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        ix.data.pop();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ExampleProgramError::InvalidInstructionData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_invalid_option_tag_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        // `new_admin` tag
+        ix.data[1] = 2;
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[&admin]).await,
+            ExampleProgramError::InvalidInstructionData
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_config_missing_account_fails() {
+        let (mut fixture, admin, base) = setup_config().await;
+
+        let mut ix = update_config_ix(&base, &admin.pubkey(), None, Some(200));
+        ix.accounts.pop();
+
+        assert_program_error!(
+            process(&mut fixture, ix, &[]).await,
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config_owned_by_program() {
+        let (fixture, _, base) = setup_config().await;
+        let (config, _) = config_address(&base);
+
+        assert_eq!(get_account(&fixture, &config).await.owner, example_program_id());
+    }
+}
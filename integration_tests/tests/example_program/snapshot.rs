@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use pinocchio_template_sdk::example_program::{
+        config_address, deserialize_config, example_program_id,
+    };
+    use solana_account_decoder::{encode_ui_account, UiAccountEncoding};
+    use solana_program::pubkey::Pubkey;
+    use solana_rpc_client_api::response::RpcKeyedAccount;
+    use solana_sdk::{
+        account::Account,
+        signature::{keypair_from_seed, Keypair, Signer},
+    };
+
+    use crate::fixtures::fixture::{load_accounts_from_dir, TestBuilder};
+
+    /// A config and its admin, as written by `solana account --output json`
+    const ACCOUNTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/accounts");
+
+    /// The snapshot's config is derived from this base, its admin is `snapshot_admin`
+    const SNAPSHOT_BASE: Pubkey = Pubkey::new_from_array([3; 32]);
+
+    fn snapshot_admin() -> Keypair {
+        keypair_from_seed(&[7; 32]).unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "{}-{}-{}",
+            name,
+            std::process::id(),
+            Pubkey::new_unique()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_account(dir: &Path, address: &Pubkey, account: &Account, encoding: UiAccountEncoding) {
+        let keyed_account = RpcKeyedAccount {
+            pubkey: address.to_string(),
+            account: encode_ui_account(address, account, encoding, None, None),
+        };
+        std::fs::write(
+            dir.join(format!("{}.json", address)),
+            serde_json::to_string_pretty(&keyed_account).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_load_accounts_from_dir() {
+        let accounts = load_accounts_from_dir(ACCOUNTS_DIR).unwrap();
+        let (config, _) = config_address(&SNAPSHOT_BASE);
+        let admin = snapshot_admin().pubkey();
+
+        assert_eq!(accounts.len(), 2);
+        let (_, config_account) = accounts.iter().find(|(a, _)| *a == config).unwrap();
+        assert_eq!(config_account.owner, example_program_id());
+
+        let config = deserialize_config(&config_account.data).unwrap();
+        assert_eq!(config.base(), &SNAPSHOT_BASE.to_bytes());
+        assert_eq!(config.admin(), &admin.to_bytes());
+        assert_eq!(config.fees_bps(), 250);
+
+        let (_, admin_account) = accounts.iter().find(|(a, _)| *a == admin).unwrap();
+        assert_eq!(admin_account.lamports, 5_000_000_000);
+    }
+
+    #[test]
+    fn test_load_accounts_round_trip() {
+        let dir = temp_dir("account-fixtures");
+        let address = Pubkey::new_unique();
+        let account = Account {
+            lamports: 42,
+            data: vec![1, 2, 3],
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: u64::MAX,
+        };
+        write_account(&dir, &address, &account, UiAccountEncoding::Base64);
+        std::fs::write(dir.join("README.txt"), "not an account").unwrap();
+
+        assert_eq!(
+            load_accounts_from_dir(&dir).unwrap(),
+            vec![(address, account)]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_accounts_json_parsed_fails() {
+        let dir = temp_dir("account-fixtures");
+        let address = Pubkey::new_unique();
+        // `--output json` of a parsable account, e.g. a token mint, carries no raw data
+        let json = serde_json::json!({
+            "pubkey": address.to_string(),
+            "account": {
+                "lamports": 1,
+                "data": { "program": "spl-token", "parsed": {}, "space": 82 },
+                "owner": spl_token::id().to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": 82,
+            },
+        });
+        std::fs::write(dir.join(format!("{}.json", address)), json.to_string()).unwrap();
+
+        let error = load_accounts_from_dir(&dir).unwrap_err();
+        assert!(error.to_string().contains(&format!("{}.json", address)));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_config_from_snapshot() {
+        let mut fixture = TestBuilder::with_accounts_from_dir(ACCOUNTS_DIR)
+            .await
+            .unwrap();
+        let admin = snapshot_admin();
+
+        let config = fixture.get_config(&SNAPSHOT_BASE).await.unwrap();
+        assert_eq!(config.admin, admin.pubkey());
+        assert_eq!(config.fees_bps, 250);
+
+        let config = fixture
+            .update_config(&SNAPSHOT_BASE, &admin, None, Some(300))
+            .await
+            .unwrap();
+        assert_eq!(config.fees_bps, 300);
+    }
+}
@@ -0,0 +1,399 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio_template_sdk::token::unpack_mint;
+    use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+    use solana_sdk::{
+        account::Account,
+        signature::{Keypair, Signer},
+    };
+    use spl_token_2022::{
+        extension::{
+            confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
+            cpi_guard::CpiGuard,
+            interest_bearing_mint::InterestBearingConfig,
+            memo_transfer::MemoTransfer,
+            mint_close_authority::MintCloseAuthority,
+            non_transferable::NonTransferable,
+            permanent_delegate::PermanentDelegate,
+            transfer_fee::TransferFeeConfig,
+            transfer_hook::TransferHook,
+            BaseStateWithExtensions, ExtensionType,
+        },
+        solana_zk_sdk::encryption::{elgamal::ElGamalKeypair, pod::elgamal::PodElGamalPubkey},
+    };
+    use test_case::test_case;
+
+    use crate::{
+        assert_program_error,
+        fixtures::{
+            fixture::{MintExtension, TestBuilder},
+            test_error::TestError,
+        },
+    };
+
+    const DELEGATE: Pubkey = Pubkey::new_from_array([1; 32]);
+    const HOOK_PROGRAM: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    const ALL_EXTENSIONS: [MintExtension; 6] = [
+        MintExtension::TransferFee {
+            transfer_fee_basis_points: 50,
+            maximum_fee: 1_000,
+        },
+        MintExtension::MintCloseAuthority,
+        MintExtension::InterestBearing { rate: 300 },
+        MintExtension::PermanentDelegate(DELEGATE),
+        MintExtension::TransferHook {
+            program_id: HOOK_PROGRAM,
+        },
+        MintExtension::NonTransferable,
+    ];
+
+    /// Incompatible with `TransferFee` without a confidential transfer fee config, so not part of
+    /// `ALL_EXTENSIONS`
+    const CONFIDENTIAL_TRANSFER: [MintExtension; 1] = [MintExtension::ConfidentialTransfer {
+        auto_approve_new_accounts: true,
+    }];
+
+    async fn get_account(fixture: &mut TestBuilder, mint: &Pubkey) -> Account {
+        fixture
+            .context
+            .banks_client
+            .get_account(*mint)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn assert_rent_exempt(fixture: &mut TestBuilder, account: &Account) {
+        let rent = fixture.context.banks_client.get_rent().await.unwrap();
+        assert_eq!(account.lamports, rent.minimum_balance(account.data.len()));
+    }
+
+    #[tokio::test]
+    async fn test_create_mint_spl_token() {
+        let mut fixture = TestBuilder::new().await;
+        let authority = Pubkey::new_unique();
+
+        let mint = fixture
+            .create_mint(&spl_token::id(), 6, &authority, &[])
+            .await
+            .unwrap();
+
+        let account = get_account(&mut fixture, &mint).await;
+        assert_eq!(account.owner, spl_token::id());
+        assert_eq!(account.data.len(), spl_token::state::Mint::LEN);
+        assert_rent_exempt(&mut fixture, &account).await;
+
+        let state = fixture.get_token_mint(&mint).await.unwrap();
+        assert!(state.is_initialized);
+        assert_eq!(state.decimals, 6);
+        assert_eq!(state.mint_authority, Some(authority).into());
+        assert_eq!(state.freeze_authority, None.into());
+    }
+
+    #[tokio::test]
+    async fn test_create_mint_spl_token_with_extensions_fails() {
+        let mut fixture = TestBuilder::new().await;
+
+        let result = fixture
+            .create_mint(
+                &spl_token::id(),
+                6,
+                &Pubkey::new_unique(),
+                &[MintExtension::MintCloseAuthority],
+            )
+            .await;
+
+        assert_program_error!(result, TestError::Client(ProgramError::IncorrectProgramId));
+    }
+
+    #[test_case(&[] ; "no extensions")]
+    #[test_case(&ALL_EXTENSIONS[0..1] ; "transfer fee")]
+    #[test_case(&ALL_EXTENSIONS[1..2] ; "mint close authority")]
+    #[test_case(&ALL_EXTENSIONS[2..3] ; "interest bearing")]
+    #[test_case(&ALL_EXTENSIONS[3..4] ; "permanent delegate")]
+    #[test_case(&ALL_EXTENSIONS[4..5] ; "transfer hook")]
+    #[test_case(&ALL_EXTENSIONS[5..6] ; "non transferable")]
+    #[test_case(&ALL_EXTENSIONS ; "all extensions")]
+    #[test_case(&CONFIDENTIAL_TRANSFER ; "confidential transfer")]
+    #[tokio::test]
+    async fn test_create_mint_token_2022(extensions: &[MintExtension]) {
+        let mut fixture = TestBuilder::new().await;
+        let authority = Pubkey::new_unique();
+
+        let mint = fixture
+            .create_mint(&spl_token_2022::id(), 9, &authority, extensions)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut fixture, &mint).await;
+        let extension_types: Vec<ExtensionType> = extensions
+            .iter()
+            .map(MintExtension::extension_type)
+            .collect();
+        assert_eq!(account.owner, spl_token_2022::id());
+        assert_eq!(
+            account.data.len(),
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+                &extension_types
+            )
+            .unwrap()
+        );
+        assert_rent_exempt(&mut fixture, &account).await;
+
+        let state = unpack_mint(account.data).unwrap();
+        assert_eq!(state.base.decimals, 9);
+        assert_eq!(state.base.mint_authority, Some(authority).into());
+        assert_eq!(state.get_extension_types().unwrap(), extension_types);
+
+        for extension in extensions {
+            match *extension {
+                MintExtension::TransferFee {
+                    transfer_fee_basis_points,
+                    maximum_fee,
+                } => {
+                    let config = state.get_extension::<TransferFeeConfig>().unwrap();
+                    let fee = config.get_epoch_fee(0);
+                    assert_eq!(
+                        u16::from(fee.transfer_fee_basis_points),
+                        transfer_fee_basis_points
+                    );
+                    assert_eq!(u64::from(fee.maximum_fee), maximum_fee);
+                    assert_eq!(
+                        config.transfer_fee_config_authority,
+                        Some(authority).try_into().unwrap()
+                    );
+                    assert_eq!(
+                        config.withdraw_withheld_authority,
+                        Some(authority).try_into().unwrap()
+                    );
+                }
+                MintExtension::MintCloseAuthority => {
+                    let close = state.get_extension::<MintCloseAuthority>().unwrap();
+                    assert_eq!(close.close_authority, Some(authority).try_into().unwrap());
+                }
+                MintExtension::InterestBearing { rate } => {
+                    let config = state.get_extension::<InterestBearingConfig>().unwrap();
+                    assert_eq!(i16::from(config.current_rate), rate);
+                    assert_eq!(config.rate_authority, Some(authority).try_into().unwrap());
+                }
+                MintExtension::PermanentDelegate(delegate) => {
+                    let permanent_delegate = state.get_extension::<PermanentDelegate>().unwrap();
+                    assert_eq!(
+                        permanent_delegate.delegate,
+                        Some(delegate).try_into().unwrap()
+                    );
+                }
+                MintExtension::TransferHook { program_id } => {
+                    let hook = state.get_extension::<TransferHook>().unwrap();
+                    assert_eq!(hook.program_id, Some(program_id).try_into().unwrap());
+                    assert_eq!(hook.authority, Some(authority).try_into().unwrap());
+                }
+                MintExtension::NonTransferable => {
+                    state.get_extension::<NonTransferable>().unwrap();
+                }
+                MintExtension::ConfidentialTransfer {
+                    auto_approve_new_accounts,
+                } => {
+                    let config = state.get_extension::<ConfidentialTransferMint>().unwrap();
+                    assert_eq!(
+                        bool::from(config.auto_approve_new_accounts),
+                        auto_approve_new_accounts
+                    );
+                    assert_eq!(config.authority, Some(authority).try_into().unwrap());
+                }
+            }
+        }
+    }
+
+    /// Extension types in a comparable order, `ExtensionType` is not `Ord`
+    fn sorted(extension_types: &[ExtensionType]) -> Vec<u16> {
+        let mut extension_types: Vec<u16> = extension_types.iter().map(|e| u16::from(*e)).collect();
+        extension_types.sort();
+        extension_types
+    }
+
+    #[tokio::test]
+    async fn test_create_token_account_spl_token() {
+        let mut fixture = TestBuilder::new().await;
+        let owner = Keypair::new();
+        let mint = fixture
+            .create_mint(&spl_token::id(), 6, &Pubkey::new_unique(), &[])
+            .await
+            .unwrap();
+
+        let state = fixture
+            .create_token_account(&spl_token::id(), &Keypair::new(), &mint, &owner, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(state.base.mint, mint);
+        assert_eq!(state.base.owner, owner.pubkey());
+        assert_eq!(state.base.amount, 0);
+    }
+
+    #[test_case(&[] ; "no extensions")]
+    #[test_case(&[ExtensionType::ImmutableOwner] ; "immutable owner")]
+    #[test_case(&[ExtensionType::MemoTransfer] ; "memo transfer")]
+    #[test_case(&[ExtensionType::CpiGuard] ; "cpi guard")]
+    #[test_case(
+        &[ExtensionType::CpiGuard, ExtensionType::ImmutableOwner, ExtensionType::MemoTransfer] ;
+        "all owner extensions"
+    )]
+    #[tokio::test]
+    async fn test_create_token_account_token_2022(extensions: &[ExtensionType]) {
+        let mut fixture = TestBuilder::new().await;
+        let owner = Keypair::new();
+        let mint = fixture
+            .create_mint(&spl_token_2022::id(), 6, &Pubkey::new_unique(), &[])
+            .await
+            .unwrap();
+
+        let account = Keypair::new();
+        let state = fixture
+            .create_token_account(&spl_token_2022::id(), &account, &mint, &owner, extensions)
+            .await
+            .unwrap();
+
+        assert_eq!(state.base.mint, mint);
+        assert_eq!(state.base.owner, owner.pubkey());
+        assert_eq!(
+            sorted(&state.get_extension_types().unwrap()),
+            sorted(extensions)
+        );
+        if extensions.contains(&ExtensionType::MemoTransfer) {
+            let memo_transfer = state.get_extension::<MemoTransfer>().unwrap();
+            assert!(bool::from(memo_transfer.require_incoming_transfer_memos));
+        }
+        if extensions.contains(&ExtensionType::CpiGuard) {
+            assert!(bool::from(
+                state.get_extension::<CpiGuard>().unwrap().lock_cpi
+            ));
+        }
+
+        let account = get_account(&mut fixture, &account.pubkey()).await;
+        assert_rent_exempt(&mut fixture, &account).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_token_account_owned_by_payer() {
+        let mut fixture = TestBuilder::new().await;
+        let payer = fixture.context.payer.insecure_clone();
+        let mint = fixture
+            .create_mint(&spl_token_2022::id(), 6, &Pubkey::new_unique(), &[])
+            .await
+            .unwrap();
+
+        let state = fixture
+            .create_token_account(
+                &spl_token_2022::id(),
+                &Keypair::new(),
+                &mint,
+                &payer,
+                &[ExtensionType::MemoTransfer, ExtensionType::CpiGuard],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(state.base.owner, payer.pubkey());
+    }
+
+    #[test_case(&[] ; "from the mint")]
+    #[test_case(
+        &[ExtensionType::TransferFeeAmount, ExtensionType::ImmutableOwner] ;
+        "listed as well"
+    )]
+    #[tokio::test]
+    async fn test_create_token_account_mint_required_extensions(extensions: &[ExtensionType]) {
+        let mut fixture = TestBuilder::new().await;
+        let mint = fixture
+            .create_mint(
+                &spl_token_2022::id(),
+                6,
+                &Pubkey::new_unique(),
+                &[ALL_EXTENSIONS[0], ALL_EXTENSIONS[4], ALL_EXTENSIONS[5]],
+            )
+            .await
+            .unwrap();
+
+        let state = fixture
+            .create_token_account(
+                &spl_token_2022::id(),
+                &Keypair::new(),
+                &mint,
+                &Keypair::new(),
+                extensions,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sorted(&state.get_extension_types().unwrap()),
+            sorted(&[
+                ExtensionType::TransferFeeAmount,
+                ExtensionType::TransferHookAccount,
+                ExtensionType::NonTransferableAccount,
+                ExtensionType::ImmutableOwner,
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_token_account_confidential_transfer() {
+        let mut fixture = TestBuilder::new().await;
+        let owner = Keypair::new();
+        let mint = fixture
+            .create_mint(
+                &spl_token_2022::id(),
+                6,
+                &Pubkey::new_unique(),
+                &CONFIDENTIAL_TRANSFER,
+            )
+            .await
+            .unwrap();
+
+        let account = Keypair::new();
+        let state = fixture
+            .create_token_account(
+                &spl_token_2022::id(),
+                &account,
+                &mint,
+                &owner,
+                &[ExtensionType::ConfidentialTransferAccount],
+            )
+            .await
+            .unwrap();
+
+        let elgamal_keypair =
+            ElGamalKeypair::new_from_signer(&owner, &account.pubkey().to_bytes()).unwrap();
+        let confidential = state
+            .get_extension::<ConfidentialTransferAccount>()
+            .unwrap();
+        assert!(bool::from(confidential.approved));
+        assert_eq!(
+            confidential.elgamal_pubkey,
+            PodElGamalPubkey::from(*elgamal_keypair.pubkey())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_token_account_mint_extension_fails() {
+        let mut fixture = TestBuilder::new().await;
+        let mint = fixture
+            .create_mint(&spl_token_2022::id(), 6, &Pubkey::new_unique(), &[])
+            .await
+            .unwrap();
+
+        let result = fixture
+            .create_token_account(
+                &spl_token_2022::id(),
+                &Keypair::new(),
+                &mint,
+                &Keypair::new(),
+                &[ExtensionType::TransferFeeConfig],
+            )
+            .await;
+
+        assert_program_error!(result, TestError::Client(ProgramError::InvalidArgument));
+    }
+}
@@ -1,11 +1,30 @@
-use std::fmt::{Debug, Formatter};
+use std::{
+    fmt::{Debug, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use pinocchio_template_sdk::example_program::example_program_id;
+use pinocchio_template_sdk::{
+    example_program::{
+        config_address, deserialize_config, example_program_id, initialize_config_ix,
+        update_config_ix,
+    },
+    test_utils::{example_program_test, example_program_test_native},
+    token::{unpack_mint, unpack_token_account},
+};
 use solana_program::{
-    clock::Clock, native_token::sol_to_lamports, pubkey::Pubkey, system_instruction::transfer,
+    clock::{Clock, Epoch, Slot, UnixTimestamp, DEFAULT_MS_PER_SLOT},
+    instruction::Instruction,
+    native_token::sol_to_lamports,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction::transfer,
 };
-use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_rpc_client_api::response::RpcKeyedAccount;
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
     signature::{Keypair, Signer},
     transaction::Transaction,
@@ -14,9 +33,166 @@ use spl_associated_token_account::{
     get_associated_token_address, instruction::create_associated_token_account_idempotent,
 };
 use spl_token_2022::{
-    extension::{ExtensionType, StateWithExtensionsOwned},
+    extension::{
+        confidential_transfer::{self, instruction::PubkeyValidityProofData},
+        cpi_guard, interest_bearing_mint, memo_transfer, transfer_fee, transfer_hook, AccountType,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensionsOwned,
+    },
     instruction::transfer_checked,
+    solana_zk_sdk::encryption::{auth_encryption::AeKey, elgamal::ElGamalKeypair},
 };
+use spl_token_confidential_transfer_proof_extraction::instruction::ProofLocation;
+
+use super::test_error::TestError;
+
+/// The spl-token CLI's default for confidential transfer accounts
+const MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER: u64 = 65536;
+
+/// Reads every `.json` file in `dir` as a `solana account --output json` account, in file name
+/// order
+pub fn load_accounts_from_dir(dir: impl AsRef<Path>) -> io::Result<Vec<(Pubkey, Account)>> {
+    let dir = dir.as_ref();
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    paths.retain(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "json")
+    });
+    paths.sort();
+
+    paths.iter().map(|path| load_account_file(path)).collect()
+}
+
+fn load_account_file(path: &Path) -> io::Result<(Pubkey, Account)> {
+    let invalid = |reason: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), reason),
+        )
+    };
+
+    let keyed_account: RpcKeyedAccount =
+        serde_json::from_str(&fs::read_to_string(path)?).map_err(|e| invalid(e.to_string()))?;
+    let address = Pubkey::from_str(&keyed_account.pubkey)
+        .map_err(|e| invalid(format!("invalid pubkey: {}", e)))?;
+    let account = keyed_account
+        .account
+        .decode::<Account>()
+        .ok_or_else(|| invalid("account data must be base58 or base64 encoded".to_string()))?;
+
+    Ok((address, account))
+}
+
+/// The decoded state of a config account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigState {
+    pub address: Pubkey,
+    pub base: Pubkey,
+    pub admin: Pubkey,
+    pub fees_bps: u64,
+    pub bump: u8,
+    pub lamports: u64,
+}
+
+/// A mint extension for `TestBuilder::create_mint`. Every authority the extension has is the
+/// mint authority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintExtension {
+    TransferFee {
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+    MintCloseAuthority,
+    InterestBearing {
+        rate: i16,
+    },
+    PermanentDelegate(Pubkey),
+    TransferHook {
+        program_id: Pubkey,
+    },
+    NonTransferable,
+    /// Without an auditor
+    ConfidentialTransfer {
+        auto_approve_new_accounts: bool,
+    },
+}
+
+impl MintExtension {
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            Self::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            Self::MintCloseAuthority => ExtensionType::MintCloseAuthority,
+            Self::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+            Self::PermanentDelegate(_) => ExtensionType::PermanentDelegate,
+            Self::TransferHook { .. } => ExtensionType::TransferHook,
+            Self::NonTransferable => ExtensionType::NonTransferable,
+            Self::ConfidentialTransfer { .. } => ExtensionType::ConfidentialTransferMint,
+        }
+    }
+
+    /// The instruction initializing the extension, which must run before `InitializeMint`
+    fn initialize_ix(
+        &self,
+        token_program_id: &Pubkey,
+        mint: &Pubkey,
+        authority: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        match *self {
+            Self::TransferFee {
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program_id,
+                mint,
+                Some(authority),
+                Some(authority),
+                transfer_fee_basis_points,
+                maximum_fee,
+            ),
+            Self::MintCloseAuthority => {
+                spl_token_2022::instruction::initialize_mint_close_authority(
+                    token_program_id,
+                    mint,
+                    Some(authority),
+                )
+            }
+            Self::InterestBearing { rate } => interest_bearing_mint::instruction::initialize(
+                token_program_id,
+                mint,
+                Some(*authority),
+                rate,
+            ),
+            Self::PermanentDelegate(delegate) => {
+                spl_token_2022::instruction::initialize_permanent_delegate(
+                    token_program_id,
+                    mint,
+                    &delegate,
+                )
+            }
+            Self::TransferHook { program_id } => transfer_hook::instruction::initialize(
+                token_program_id,
+                mint,
+                Some(*authority),
+                Some(program_id),
+            ),
+            Self::NonTransferable => spl_token_2022::instruction::initialize_non_transferable_mint(
+                token_program_id,
+                mint,
+            ),
+            Self::ConfidentialTransfer {
+                auto_approve_new_accounts,
+            } => confidential_transfer::instruction::initialize_mint(
+                token_program_id,
+                mint,
+                Some(*authority),
+                auto_approve_new_accounts,
+                None,
+            ),
+        }
+    }
+}
 
 pub struct TestBuilder {
     pub context: ProgramTestContext,
@@ -31,23 +207,37 @@ impl Debug for TestBuilder {
 impl TestBuilder {
     pub async fn new() -> Self {
         // $ cargo-build-sbf && SBF_OUT_DIR=$(pwd)/target/sbf-solana-solana/release cargo nextest run
-        let mut program_test = ProgramTest::new(
-            "pinocchio_template_example_program",
-            example_program_id(),
-            None,
-        );
+        let program_test = example_program_test();
 
-        program_test.prefer_bpf(true);
+        let context = program_test.start_with_context().await;
+
+        Self { context }
+    }
+
+    /// Like `new`, with every `solana account --output json` file in `dir` loaded into the bank
+    /// before it starts, e.g. the output of the CLI's `dump-config`
+    pub async fn with_accounts_from_dir(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut program_test = example_program_test();
+        for (address, account) in load_accounts_from_dir(dir)? {
+            program_test.add_account(address, account);
+        }
 
         let context = program_test.start_with_context().await;
 
+        Ok(Self { context })
+    }
+
+    /// Like `new`, with the program running natively as a builtin instead of the SBF binary
+    pub async fn new_native() -> Self {
+        let context = example_program_test_native().start_with_context().await;
+
         Self { context }
     }
-    
 
     pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<(), BanksClientError> {
         let blockhash = self.context.banks_client.get_latest_blockhash().await?;
-        self.context.banks_client
+        self.context
+            .banks_client
             .process_transaction_with_preflight_and_commitment(
                 Transaction::new_signed_with_payer(
                     &[transfer(&self.context.payer.pubkey(), to, lamports)],
@@ -100,9 +290,7 @@ impl TestBuilder {
             .get_account(*mint)
             .await?
             .ok_or(BanksClientError::ClientError("failed to get mint account"))?;
-        let mint_account =
-            StateWithExtensionsOwned::<spl_token_2022::state::Mint>::unpack(mint_account_raw.data)
-                .unwrap();
+        let mint_account = unpack_mint(mint_account_raw.data).unwrap();
 
         let source_token_account = get_associated_token_address(&source.pubkey(), mint);
         let destination_token_account = get_associated_token_address(destination, mint);
@@ -123,7 +311,7 @@ impl TestBuilder {
                     )
                     .unwrap()],
                     Some(&self.context.payer.pubkey()),
-                    &[&source, &self.context.payer],
+                    &[source, &self.context.payer],
                     blockhash,
                 ),
                 CommitmentLevel::Processed,
@@ -131,104 +319,192 @@ impl TestBuilder {
             .await
     }
 
+    /// Creates a token account for `mint` owned by `owner`, with `extensions` and the extensions
+    /// the mint requires on its accounts, and returns its decoded state. MemoTransfer, CpiGuard and
+    /// ConfidentialTransferAccount are enabled by `owner`, the confidential transfer keys are
+    /// derived from `owner` and the account address.
     pub async fn create_token_account(
         &mut self,
         token_program_id: &Pubkey,
         account: &Keypair,
-        pool_mint: &Pubkey,
-        owner: &Pubkey,
+        mint: &Pubkey,
+        owner: &Keypair,
         extensions: &[ExtensionType],
-    ) -> Result<(), BanksClientError> {
-        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+    ) -> Result<StateWithExtensionsOwned<spl_token_2022::state::Account>, TestError> {
+        let address = account.pubkey();
         let rent = self.context.banks_client.get_rent().await?;
-        let space =
-            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(extensions)
-                .map_err(|_e| {
-                    BanksClientError::ClientError("failed to try calculate account length")
-                })?;
-        let account_rent = rent.minimum_balance(space);
+        let mint_account = self
+            .context
+            .banks_client
+            .get_account(*mint)
+            .await?
+            .ok_or(TestError::AccountNotFound(*mint))?;
+        let mint_extensions = unpack_mint(mint_account.data)
+            .map_err(|_| TestError::InvalidAccountData(*mint))?
+            .get_extension_types()?;
+
+        let mut extension_types =
+            ExtensionType::get_required_init_account_extensions(&mint_extensions);
+        if extensions.contains(&ExtensionType::ConfidentialTransferAccount)
+            && mint_extensions.contains(&ExtensionType::ConfidentialTransferFeeConfig)
+        {
+            extension_types.push(ExtensionType::ConfidentialTransferFeeAmount);
+        }
+        for extension in extensions {
+            if extension.get_account_type() != AccountType::Account {
+                return Err(ProgramError::InvalidArgument.into());
+            }
+            if !extension_types.contains(extension) {
+                extension_types.push(*extension);
+            }
+        }
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &extension_types,
+        )?;
 
         let mut instructions = vec![solana_program::system_instruction::create_account(
             &self.context.payer.pubkey(),
-            &account.pubkey(),
-            account_rent,
+            &address,
+            rent.minimum_balance(space),
             space as u64,
             token_program_id,
         )];
 
+        // Extensions which must be initialized before `InitializeAccount`
         for extension in extensions {
-            match extension {
-                ExtensionType::ImmutableOwner => instructions.push(
-                    spl_token_2022::instruction::initialize_immutable_owner(
-                        token_program_id,
-                        &account.pubkey(),
-                    )
-                    .unwrap(),
-                ),
-                ExtensionType::TransferFeeAmount
-                | ExtensionType::MemoTransfer
-                | ExtensionType::CpiGuard
-                | ExtensionType::NonTransferableAccount => (),
-                _ => unimplemented!(),
-            };
+            if *extension == ExtensionType::ImmutableOwner {
+                instructions.push(spl_token_2022::instruction::initialize_immutable_owner(
+                    token_program_id,
+                    &address,
+                )?);
+            }
         }
 
-        instructions.push(
-            spl_token_2022::instruction::initialize_account(
-                token_program_id,
-                &account.pubkey(),
-                pool_mint,
-                owner,
-            )
-            .map_err(|_e| BanksClientError::ClientError("failed to initialize account"))?,
-        );
+        instructions.push(spl_token_2022::instruction::initialize_account3(
+            token_program_id,
+            &address,
+            mint,
+            &owner.pubkey(),
+        )?);
 
-        let mut signers = vec![&self.context.payer, account];
+        // Extensions enabled by the owner once the account is initialized
         for extension in extensions {
             match extension {
                 ExtensionType::MemoTransfer => {
-                    signers.push(&self.context.payer);
-                    instructions.push(
-                spl_token_2022::extension::memo_transfer::instruction::enable_required_transfer_memos(
-                    token_program_id,
-                    &account.pubkey(),
-                    &self.context.payer.pubkey(),
-                    &[],
-                ).map_err(|_e| BanksClientError::ClientError("failed to enable required transfer memos"))?
-                )
+                    instructions.push(memo_transfer::instruction::enable_required_transfer_memos(
+                        token_program_id,
+                        &address,
+                        &owner.pubkey(),
+                        &[],
+                    )?)
                 }
                 ExtensionType::CpiGuard => {
-                    signers.push(&self.context.payer);
-                    instructions.push(
-                        spl_token_2022::extension::cpi_guard::instruction::enable_cpi_guard(
-                            token_program_id,
-                            &account.pubkey(),
-                            &self.context.payer.pubkey(),
-                            &[],
-                        )
-                        .map_err(|_e| {
-                            BanksClientError::ClientError("failed to enable cpi guard")
-                        })?,
-                    )
+                    instructions.push(cpi_guard::instruction::enable_cpi_guard(
+                        token_program_id,
+                        &address,
+                        &owner.pubkey(),
+                        &[],
+                    )?)
                 }
+                ExtensionType::ConfidentialTransferAccount => {
+                    let seed = address.to_bytes();
+                    let elgamal_keypair = ElGamalKeypair::new_from_signer(owner, &seed)
+                        .map_err(|_| ProgramError::InvalidArgument)?;
+                    let ae_key = AeKey::new_from_signer(owner, &seed)
+                        .map_err(|_| ProgramError::InvalidArgument)?;
+                    let pubkey_validity_proof = PubkeyValidityProofData::new(&elgamal_keypair)
+                        .map_err(|_| ProgramError::InvalidArgument)?;
+
+                    instructions.extend(confidential_transfer::instruction::configure_account(
+                        token_program_id,
+                        &address,
+                        mint,
+                        &ae_key.encrypt(0).into(),
+                        MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER,
+                        &owner.pubkey(),
+                        &[],
+                        ProofLocation::InstructionOffset(
+                            1.try_into().unwrap(),
+                            &pubkey_validity_proof,
+                        ),
+                    )?);
+                }
+                // Initialized from the mint by `InitializeAccount` and `ConfigureAccount`
                 ExtensionType::ImmutableOwner
                 | ExtensionType::TransferFeeAmount
-                | ExtensionType::NonTransferableAccount => (),
-                _ => unimplemented!(),
+                | ExtensionType::NonTransferableAccount
+                | ExtensionType::TransferHookAccount
+                | ExtensionType::PausableAccount
+                | ExtensionType::ConfidentialTransferFeeAmount => (),
+                _ => unreachable!("mint extensions are rejected above"),
             }
         }
 
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&self.context.payer.pubkey()),
-            &signers,
-            blockhash,
-        );
+        let owner_signs = instructions
+            .iter()
+            .flat_map(|ix| &ix.accounts)
+            .any(|meta| meta.is_signer && meta.pubkey == owner.pubkey());
+        let signers: &[&Keypair] = if owner_signs {
+            &[account, owner]
+        } else {
+            &[account]
+        };
+        self.process_instructions(&instructions, signers).await?;
 
-        self.context
+        let account = self
+            .context
             .banks_client
-            .process_transaction(transaction)
-            .await
+            .get_account(address)
+            .await?
+            .ok_or(TestError::AccountNotFound(address))?;
+
+        unpack_token_account(account.data).map_err(|_| TestError::InvalidAccountData(address))
+    }
+
+    /// Creates a mint owned by `token_program_id` with `authority` as mint authority and no
+    /// freeze authority. Extensions are only supported by Token-2022.
+    pub async fn create_mint(
+        &mut self,
+        token_program_id: &Pubkey,
+        decimals: u8,
+        authority: &Pubkey,
+        extensions: &[MintExtension],
+    ) -> Result<Pubkey, TestError> {
+        let mint = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await?;
+        let extension_types: Vec<ExtensionType> = extensions
+            .iter()
+            .map(MintExtension::extension_type)
+            .collect();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &extension_types,
+        )?;
+
+        let mut instructions = vec![solana_program::system_instruction::create_account(
+            &self.context.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            token_program_id,
+        )];
+        for extension in extensions {
+            instructions.push(extension.initialize_ix(
+                token_program_id,
+                &mint.pubkey(),
+                authority,
+            )?);
+        }
+        instructions.push(spl_token_2022::instruction::initialize_mint2(
+            token_program_id,
+            &mint.pubkey(),
+            authority,
+            None,
+            decimals,
+        )?);
+
+        self.process_instructions(&instructions, &[&mint]).await?;
+
+        Ok(mint.pubkey())
     }
 
     pub async fn get_token_account(
@@ -242,9 +518,8 @@ impl TestBuilder {
             .await?
             .ok_or(BanksClientError::ClientError("failed to get token account"))?;
 
-        let account_info =
-            StateWithExtensionsOwned::<spl_token_2022::state::Account>::unpack(account.data)
-                .map_err(|_e| BanksClientError::ClientError("failed to unpack"))?;
+        let account_info = unpack_token_account(account.data)
+            .map_err(|_e| BanksClientError::ClientError("failed to unpack"))?;
 
         Ok(account_info.base)
     }
@@ -260,9 +535,8 @@ impl TestBuilder {
             .await?
             .ok_or(BanksClientError::ClientError("failed to get token account"))?;
 
-        let account_info =
-            StateWithExtensionsOwned::<spl_token_2022::state::Mint>::unpack(account.data)
-                .map_err(|_e| BanksClientError::ClientError("failed to unpack"))?;
+        let account_info = unpack_mint(account.data)
+            .map_err(|_e| BanksClientError::ClientError("failed to unpack"))?;
 
         Ok(account_info.base)
     }
@@ -368,4 +642,195 @@ impl TestBuilder {
         Ok(clock.slot)
     }
 
+    pub async fn get_clock(&mut self) -> Result<Clock, BanksClientError> {
+        self.context.banks_client.get_sysvar().await
+    }
+
+    /// Overrides the `Clock` sysvar, warping first when `clock.slot` is ahead of the bank. The
+    /// epochs must be the ones of `clock.slot` and the epoch cannot start after `unix_timestamp`,
+    /// so `Clock::get()` in the program never sees a clock the bank could not have produced.
+    pub async fn set_clock(&mut self, clock: &Clock) -> Result<(), BanksClientError> {
+        let epoch_schedule = &self.context.genesis_config().epoch_schedule;
+        if clock.epoch != epoch_schedule.get_epoch(clock.slot)
+            || clock.leader_schedule_epoch != epoch_schedule.get_leader_schedule_epoch(clock.slot)
+        {
+            return Err(BanksClientError::ClientError(
+                "clock epoch does not match its slot",
+            ));
+        }
+        if clock.epoch_start_timestamp > clock.unix_timestamp {
+            return Err(BanksClientError::ClientError(
+                "clock epoch starts after its timestamp",
+            ));
+        }
+
+        let current_slot = self.get_current_slot().await?;
+        if clock.slot < current_slot {
+            return Err(BanksClientError::ClientError("cannot warp to a past slot"));
+        }
+        if clock.slot > current_slot {
+            self.warp_to_slot(clock.slot).await?;
+        }
+
+        self.context.set_sysvar(clock);
+        Ok(())
+    }
+
+    /// Sets the clock's `unix_timestamp` without moving the slot. A timestamp before the start of
+    /// the epoch moves the start of the epoch back with it.
+    pub async fn set_unix_timestamp(
+        &mut self,
+        unix_timestamp: UnixTimestamp,
+    ) -> Result<(), BanksClientError> {
+        let mut clock = self.get_clock().await?;
+        clock.unix_timestamp = unix_timestamp;
+        clock.epoch_start_timestamp = clock.epoch_start_timestamp.min(unix_timestamp);
+
+        self.set_clock(&clock).await
+    }
+
+    /// Moves the clock `seconds` ahead, along with the slots produced in that time
+    pub async fn advance_seconds(&mut self, seconds: u64) -> Result<(), BanksClientError> {
+        let clock = self.get_clock().await?;
+        let slots = seconds * 1_000 / DEFAULT_MS_PER_SLOT;
+
+        self.warp_clock(
+            clock.slot + slots,
+            clock.unix_timestamp + seconds as UnixTimestamp,
+        )
+        .await
+    }
+
+    /// Warps to the first slot of `epoch`, moving the clock ahead by the time those slots take
+    pub async fn warp_to_epoch(&mut self, epoch: Epoch) -> Result<(), BanksClientError> {
+        let clock = self.get_clock().await?;
+        let slot = self
+            .context
+            .genesis_config()
+            .epoch_schedule
+            .get_first_slot_in_epoch(epoch);
+        if slot <= clock.slot {
+            return Err(BanksClientError::ClientError("cannot warp to a past epoch"));
+        }
+
+        let unix_timestamp = clock.unix_timestamp + Self::slots_duration(slot - clock.slot);
+        self.warp_clock(slot, unix_timestamp).await
+    }
+
+    /// Whole seconds taken by `slots` slots. `ProgramTest` ticks much faster than a cluster, the
+    /// clock follows the cluster's slot time so durations mean the same as on chain.
+    fn slots_duration(slots: Slot) -> UnixTimestamp {
+        (slots * DEFAULT_MS_PER_SLOT / 1_000) as UnixTimestamp
+    }
+
+    /// Warps to `slot` with the clock at `unix_timestamp`, starting a new epoch at the timestamp
+    /// of its first slot
+    async fn warp_clock(
+        &mut self,
+        slot: Slot,
+        unix_timestamp: UnixTimestamp,
+    ) -> Result<(), BanksClientError> {
+        let clock = self.get_clock().await?;
+        let epoch_schedule = self.context.genesis_config().epoch_schedule.clone();
+        let epoch = epoch_schedule.get_epoch(slot);
+        let epoch_start_timestamp = if epoch == clock.epoch {
+            clock.epoch_start_timestamp
+        } else {
+            let slots_into_epoch = slot - epoch_schedule.get_first_slot_in_epoch(epoch);
+            unix_timestamp - Self::slots_duration(slots_into_epoch)
+        };
+
+        self.set_clock(&Clock {
+            slot,
+            epoch_start_timestamp,
+            epoch,
+            leader_schedule_epoch: epoch_schedule.get_leader_schedule_epoch(slot),
+            unix_timestamp,
+        })
+        .await
+    }
+
+    /// Sends `instructions` paid for by the context payer, signed by `signers` as well
+    pub async fn process_instructions(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TestError> {
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend(
+            signers
+                .iter()
+                .filter(|signer| signer.pubkey() != self.context.payer.pubkey()),
+        );
+
+        self.context
+            .banks_client
+            .process_transaction_with_preflight_and_commitment(
+                Transaction::new_signed_with_payer(
+                    instructions,
+                    Some(&self.context.payer.pubkey()),
+                    &all_signers,
+                    blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Creates the config derived from `base` with `admin` as admin
+    pub async fn initialize_config(
+        &mut self,
+        base: &Pubkey,
+        admin: &Keypair,
+        fees_bps: u64,
+    ) -> Result<ConfigState, TestError> {
+        let ix = initialize_config_ix(base, &admin.pubkey(), fees_bps);
+        self.process_instructions(&[ix], &[admin]).await?;
+
+        self.get_config(base).await
+    }
+
+    /// Updates the config derived from `base`, signed by `admin`
+    pub async fn update_config(
+        &mut self,
+        base: &Pubkey,
+        admin: &Keypair,
+        new_admin: Option<Pubkey>,
+        new_fees_bps: Option<u64>,
+    ) -> Result<ConfigState, TestError> {
+        let ix = update_config_ix(base, &admin.pubkey(), new_admin, new_fees_bps);
+        self.process_instructions(&[ix], &[admin]).await?;
+
+        self.get_config(base).await
+    }
+
+    /// Fetches and decodes the config derived from `base`
+    pub async fn get_config(&mut self, base: &Pubkey) -> Result<ConfigState, TestError> {
+        let (address, _) = config_address(base);
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await?
+            .ok_or(TestError::AccountNotFound(address))?;
+
+        if account.owner != example_program_id() {
+            return Err(TestError::InvalidAccountData(address));
+        }
+        let config = deserialize_config(&account.data)
+            .map_err(|_| TestError::InvalidAccountData(address))?;
+
+        Ok(ConfigState {
+            address,
+            base: Pubkey::new_from_array(*config.base()),
+            admin: Pubkey::new_from_array(*config.admin()),
+            fees_bps: config.fees_bps(),
+            bump: config.bump(),
+            lamports: account.lamports,
+        })
+    }
 }
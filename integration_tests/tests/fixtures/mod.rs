@@ -1 +1,4 @@
-pub mod fixture;
\ No newline at end of file
+#[allow(dead_code)]
+pub mod fixture;
+#[allow(dead_code)]
+pub mod test_error;
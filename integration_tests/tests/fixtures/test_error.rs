@@ -0,0 +1,177 @@
+use std::fmt::{Display, Formatter};
+
+use pinocchio_template_sdk::example_program::{errors::ExampleProgramError, example_program_id};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_program_test::BanksClientError;
+use solana_sdk::transaction::TransactionError;
+
+/// Why a `TestBuilder` call failed, split so negative tests can match on what the program returned
+#[derive(Debug)]
+pub enum TestError {
+    /// The banks client failed before the program produced a result
+    Transport(BanksClientError),
+    /// The transaction failed outside of any instruction, e.g. a missing signature, or the
+    /// program that failed is unknown because the banks client returned no logs
+    Transaction(TransactionError),
+    /// The example program failed with one of its custom errors
+    ExampleProgram(ExampleProgramError),
+    /// `program_id` failed with `error`, e.g. a builtin error of the example program or a custom
+    /// error of a program it invoked
+    Program {
+        program_id: Pubkey,
+        error: ProgramError,
+    },
+    /// The fixture could not build the transaction, e.g. an instruction builder rejected its
+    /// arguments
+    Client(ProgramError),
+    AccountNotFound(Pubkey),
+    /// The account exists but does not hold the expected state
+    InvalidAccountData(Pubkey),
+}
+
+impl TestError {
+    /// `program_id` failed with `error`, decoding the example program's custom errors
+    pub fn program(program_id: Pubkey, error: ProgramError) -> Self {
+        if program_id == example_program_id() {
+            if let ProgramError::Custom(code) = error {
+                if let Ok(error) = ExampleProgramError::try_from(code) {
+                    return Self::ExampleProgram(error);
+                }
+            }
+        }
+
+        Self::Program { program_id, error }
+    }
+
+    /// Maps a failed transaction to the error of the program that failed first, which is the
+    /// innermost program when the error came from a CPI, found through its
+    /// `Program <id> failed: <error>` log
+    fn from_transaction_error(error: TransactionError, logs: &[String]) -> Self {
+        let TransactionError::InstructionError(_, instruction_error) = &error else {
+            return Self::Transaction(error);
+        };
+        // Runtime errors such as privilege escalation have no `ProgramError` equivalent
+        let Ok(program_error) = ProgramError::try_from(instruction_error.clone()) else {
+            return Self::Transaction(error);
+        };
+        let failed_program = logs.iter().find_map(|log| {
+            let (program_id, _) = log.strip_prefix("Program ")?.split_once(" failed: ")?;
+            program_id.parse::<Pubkey>().ok()
+        });
+
+        match failed_program {
+            Some(program_id) => Self::program(program_id, program_error),
+            None => Self::Transaction(error),
+        }
+    }
+}
+
+impl Display for TestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport(error) => write!(f, "transport error: {}", error),
+            Self::Transaction(error) => write!(f, "transaction error: {}", error),
+            Self::ExampleProgram(error) => write!(f, "example program error: {:?}", error),
+            Self::Program { program_id, error } => {
+                write!(f, "program {} error: {}", program_id, error)
+            }
+            Self::Client(error) => write!(f, "client error: {}", error),
+            Self::AccountNotFound(address) => write!(f, "account {} not found", address),
+            Self::InvalidAccountData(address) => write!(f, "account {} has invalid data", address),
+        }
+    }
+}
+
+impl PartialEq for TestError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // `BanksClientError` is not comparable, transport failures are never expected
+            (Self::Transport(_), Self::Transport(_)) => false,
+            (Self::Transaction(a), Self::Transaction(b)) => a == b,
+            (Self::ExampleProgram(a), Self::ExampleProgram(b)) => a == b,
+            (
+                Self::Program {
+                    program_id: a_program_id,
+                    error: a,
+                },
+                Self::Program {
+                    program_id: b_program_id,
+                    error: b,
+                },
+            ) => a_program_id == b_program_id && a == b,
+            (Self::Client(a), Self::Client(b)) => a == b,
+            (Self::AccountNotFound(a), Self::AccountNotFound(b)) => a == b,
+            (Self::InvalidAccountData(a), Self::InvalidAccountData(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<BanksClientError> for TestError {
+    fn from(error: BanksClientError) -> Self {
+        match error {
+            BanksClientError::SimulationError { err, logs, .. } => {
+                Self::from_transaction_error(err, &logs)
+            }
+            BanksClientError::TransactionError(error) => Self::from_transaction_error(error, &[]),
+            error => Self::Transport(error),
+        }
+    }
+}
+
+impl From<ExampleProgramError> for TestError {
+    fn from(error: ExampleProgramError) -> Self {
+        Self::ExampleProgram(error)
+    }
+}
+
+/// Instruction builders used by the fixture fail with `ProgramError`s
+impl From<ProgramError> for TestError {
+    fn from(error: ProgramError) -> Self {
+        Self::Client(error)
+    }
+}
+
+/// Asserts that a `TestBuilder` result failed with the given error. An `ExampleProgramError` or
+/// `ProgramError` is expected from the example program, `program_id, error` from another program
+/// and a `TestError` as is.
+#[macro_export]
+macro_rules! assert_program_error {
+    ($result:expr, $program_id:expr, $expected:expr $(,)?) => {{
+        let expected = $crate::fixtures::test_error::TestError::program($program_id, $expected);
+        match $result {
+            Ok(value) => panic!("expected {:?}, got Ok({:?})", expected, value),
+            Err(error) => assert_eq!(error, expected),
+        }
+    }};
+    ($result:expr, $expected:expr $(,)?) => {{
+        let expected = $crate::fixtures::test_error::ExpectedError::expected($expected);
+        match $result {
+            Ok(value) => panic!("expected {:?}, got Ok({:?})", expected, value),
+            Err(error) => assert_eq!(error, expected),
+        }
+    }};
+}
+
+/// What `assert_program_error!` expects for an error without a program id
+pub trait ExpectedError {
+    fn expected(self) -> TestError;
+}
+
+impl ExpectedError for ExampleProgramError {
+    fn expected(self) -> TestError {
+        TestError::ExampleProgram(self)
+    }
+}
+
+impl ExpectedError for ProgramError {
+    fn expected(self) -> TestError {
+        TestError::program(example_program_id(), self)
+    }
+}
+
+impl ExpectedError for TestError {
+    fn expected(self) -> TestError {
+        self
+    }
+}
@@ -1,21 +1,15 @@
-use pinocchio::{
-    account_info::AccountInfo,
-    instruction::Seed,
-    program_error::ProgramError,
-    pubkey::{self, Pubkey},
-};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 use pinocchio_log::log;
 
 use crate::{
     accounts::ExampleProgramDiscriminator,
     instructions::{initialize_config::InitializeConfigIxData, update_config::UpdateConfigIxData},
-    utils::{
-        load_account, load_account_mut, load_account_mut_unchecked, load_signer, DataLen,
-        Initialized,
-    },
+    pda::{Pda, PdaAccount},
+    pod::{Pod, PodU64},
+    utils::{load_account_mut, load_account_mut_unchecked, DataLen, Initialized},
 };
 
-/// The Counter account structure
+/// The Config account structure
 #[derive(Debug, Default, Copy, Clone)]
 #[repr(C)]
 pub struct Config {
@@ -23,9 +17,13 @@ pub struct Config {
     bump: u8,
     base: Pubkey,
     admin: Pubkey,
-    fees_bps: u64,
+    /// Where `fees_bps` was aligned to 8 before the `Pod` types, kept so existing configs load
+    _padding: [u8; 6],
+    fees_bps: PodU64,
 }
 
+unsafe impl Pod for Config {}
+
 impl DataLen for Config {
     const LEN: usize = core::mem::size_of::<Config>();
 }
@@ -36,121 +34,39 @@ impl Initialized for Config {
     }
 }
 
-#[macro_export]
-macro_rules! config_seed_with_bump {
-    ($base:expr, $bump_slice:expr) => {
-        [
-            crate::accounts::config::Config::SEED,
-            $base.as_ref(),
-            $bump_slice,
-        ]
-    };
-}
+impl Pda<3> for Config {
+    const NAME: &'static str = "Config";
 
-impl Config {
-    // ----------------------- ACCOUNT CHECKS ---------------------------
-    pub const SEED: &[u8] = b"CONFIG";
-
-    pub fn create_program_address(
-        program_id: &Pubkey,
-        base: &Pubkey,
-        bump: u8,
-    ) -> Result<Pubkey, ProgramError> {
-        let bump_bytes = [bump];
-        let seed_with_bump = config_seed_with_bump!(base, &bump_bytes);
-        let pda = pubkey::create_program_address(&seed_with_bump, program_id)?;
+    /// The config's base
+    type Key = Pubkey;
 
-        Ok(pda)
+    fn seeds<'a>(base: &'a Pubkey, bump: &'a [u8; 1]) -> [&'a [u8]; 3] {
+        [Self::SEED, base, bump]
     }
+}
 
-    // Sanity check for the seeds
-    pub fn check_seeds(base: &Pubkey, bump: u8, seeds: &[Seed]) -> Result<(), ProgramError> {
-        let bump_bytes = [bump];
-        let seed_with_bump = config_seed_with_bump!(base, &bump_bytes);
-
-        if seeds.len() != seed_with_bump.len() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        for (seed_index, seed) in seeds.iter().enumerate() {
-            for (byte_index, byte) in seed.as_ref().iter().enumerate() {
-                let seed_byte = seed_with_bump[seed_index][byte_index];
-                if byte.ne(&seed_byte) {
-                    return Err(ProgramError::InvalidAccountData);
-                }
-            }
-        }
-
-        Ok(())
+impl PdaAccount<3> for Config {
+    fn pda_key(&self) -> &Pubkey {
+        &self.base
     }
 
-    pub fn load(
-        program_id: &Pubkey,
-        account_info: &AccountInfo,
-        expect_writable: bool,
-        check_admin: Option<&AccountInfo>,
-    ) -> Result<(), ProgramError> {
-        let account_owner = unsafe { account_info.owner() };
-        if account_owner.ne(program_id) {
-            log!("Config account has an invalid owner");
-            return Err(ProgramError::InvalidAccountOwner);
-        }
-
-        if expect_writable && !account_info.is_writable() {
-            log!("Config account is not writable");
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let account = unsafe {
-            let data = account_info.borrow_data_unchecked();
-            let result = load_account::<Config>(data);
-
-            if let Err(error) = result {
-                log!("Config account could not be deseralized");
-                return Err(error);
-            }
-            result?
-        };
-
-        let account_key = Self::create_program_address(program_id, &account.base, account.bump)?;
-        if account_info.key().ne(&account_key) {
-            log!("Config account has an invalid key");
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if let Some(admin) = check_admin {
-            load_signer(admin, true)?;
-            if account.admin.ne(admin.key()) {
-                log!("Config account has an invalid admin");
-                return Err(ProgramError::InvalidAccountData);
-            }
-        }
-
-        Ok(())
+    fn pda_bump(&self) -> u8 {
+        self.bump
     }
+}
 
-    pub unsafe fn check_admin(
-        account_info: &AccountInfo,
-        admin: &AccountInfo,
-    ) -> Result<(), ProgramError> {
-        let mut data = account_info.borrow_mut_data_unchecked();
-        let account = load_account_mut_unchecked::<Config>(&mut data)?;
-
-        if account.admin.ne(admin.key()) {
-            log!("Config account has an invalid admin");
-            return Err(ProgramError::InvalidAccountData);
-        }
+impl Config {
+    // ----------------------- SEEDS ---------------------------
+    pub const SEED: &[u8] = b"CONFIG";
 
-        Ok(())
-    }
     // ----------------------- INITIALIZE ------------------------
-    pub unsafe fn initialize(
+    pub fn initialize(
         account_info: &AccountInfo,
         base: &Pubkey,
         admin: &Pubkey,
         ix_data: &InitializeConfigIxData,
     ) -> Result<(), ProgramError> {
-        let mut data = account_info.borrow_mut_data_unchecked();
+        let mut data = account_info.try_borrow_mut_data()?;
         let account = load_account_mut_unchecked::<Config>(&mut data)?;
 
         if account.is_initialized() {
@@ -168,18 +84,18 @@ impl Config {
     }
 
     // ----------------------- UPDATE ----------------------------
-    pub unsafe fn update(
+    pub fn update(
         account_info: &AccountInfo,
         ix_data: &UpdateConfigIxData,
     ) -> Result<(), ProgramError> {
-        let mut data = account_info.borrow_mut_data_unchecked();
+        let mut data = account_info.try_borrow_mut_data()?;
         let account = load_account_mut::<Config>(&mut data)?;
 
-        if let Some(new_admin) = ix_data.new_admin {
+        if let Some(new_admin) = ix_data.new_admin.get() {
             account.admin = new_admin;
         }
 
-        if let Some(new_fees_bps) = ix_data.new_fees_bps {
+        if let Some(new_fees_bps) = ix_data.new_fees_bps.get() {
             account.fees_bps = new_fees_bps;
         }
 
@@ -201,8 +117,88 @@ impl Config {
     }
 
     pub fn fees_bps(&self) -> u64 {
-        self.fees_bps
+        self.fees_bps.get()
     }
 
     // ----------------------- SETTERS ---------------------------
 }
+
+#[cfg(test)]
+mod tests {
+    use core::mem::{offset_of, size_of};
+    use std::str::FromStr;
+
+    use solana_pubkey::Pubkey as Address;
+
+    use super::*;
+    use crate::{golden::layout, utils::load_account};
+
+    fn address(base58: &str) -> Pubkey {
+        Address::from_str(base58).unwrap().to_bytes()
+    }
+
+    #[test]
+    fn test_config_layout() {
+        layout("Config").assert_fields(
+            Config::LEN,
+            &[
+                (
+                    "discriminator",
+                    offset_of!(Config, discriminator),
+                    size_of::<u8>(),
+                ),
+                ("bump", offset_of!(Config, bump), size_of::<u8>()),
+                ("base", offset_of!(Config, base), size_of::<Pubkey>()),
+                ("admin", offset_of!(Config, admin), size_of::<Pubkey>()),
+                (
+                    "fees_bps",
+                    offset_of!(Config, fees_bps),
+                    size_of::<PodU64>(),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_config_golden_decode() {
+        for vector in layout("Config").vectors {
+            let config = load_account::<Config>(&vector.bytes).unwrap();
+
+            assert_eq!(
+                (
+                    vector.u8("discriminator"),
+                    vector.u8("bump"),
+                    address(vector.address("base")),
+                    address(vector.address("admin")),
+                    vector.u64("fees_bps")
+                ),
+                (
+                    config.discriminator,
+                    config.bump(),
+                    *config.base(),
+                    *config.admin(),
+                    config.fees_bps()
+                ),
+                "Config {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_golden_encode() {
+        let layout = layout("Config");
+        for vector in &layout.vectors {
+            // Written field by field into zeroed account data, like `initialize` does
+            let mut bytes = vec![0; Config::LEN];
+            let config = load_account_mut_unchecked::<Config>(&mut bytes).unwrap();
+            config.discriminator = vector.u8("discriminator");
+            config.bump = vector.u8("bump");
+            config.base = address(vector.address("base"));
+            config.admin = address(vector.address("admin"));
+            config.fees_bps = vector.u64("fees_bps").into();
+
+            layout.assert_bytes_eq(&vector.name, &vector.bytes, &bytes);
+        }
+    }
+}
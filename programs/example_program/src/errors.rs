@@ -1,10 +1,14 @@
 use pinocchio::program_error::ProgramError;
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ExampleProgramError {
+    /// Unknown instruction discriminator
     InvalidInstruction,
+    /// Instruction data has the wrong length
     InvalidInstructionData,
+    /// Arithmetic overflow
     ArithmeticOverflow,
+    /// Arithmetic underflow
     ArithmeticUnderflow,
 }
 
@@ -13,3 +17,17 @@ impl From<ExampleProgramError> for ProgramError {
         Self::Custom(e as u32)
     }
 }
+
+impl TryFrom<u32> for ExampleProgramError {
+    type Error = ProgramError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ExampleProgramError::InvalidInstruction),
+            1 => Ok(ExampleProgramError::InvalidInstructionData),
+            2 => Ok(ExampleProgramError::ArithmeticOverflow),
+            3 => Ok(ExampleProgramError::ArithmeticUnderflow),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Golden byte layouts of the program's account and instruction data, from
+//! `golden/layouts.json`. Shared with the SDK tests in `sdk/tests` through `#[path]`, so it only
+//! depends on `std` and `serde_json`.
+//!
+//! Each layout lists its fields by offset and size, bytes not covered by a field are padding and
+//! are zero in every vector. Hex strings may be split with whitespace, at field boundaries by
+//! convention.
+
+// Each crate including this file uses a subset of it
+#![allow(dead_code)]
+
+use std::{fmt::Write, ops::Range};
+
+use serde_json::{Map, Value};
+
+const LAYOUTS: &str = include_str!("../golden/layouts.json");
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl Field {
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+#[derive(Debug)]
+pub struct Vector {
+    pub name: String,
+    pub bytes: Vec<u8>,
+    values: Map<String, Value>,
+}
+
+#[derive(Debug)]
+pub struct Layout {
+    pub name: String,
+    pub len: usize,
+    pub fields: Vec<Field>,
+    pub vectors: Vec<Vector>,
+}
+
+/// Loads the layout of `name`, panicking if the golden file is malformed
+pub fn layout(name: &str) -> Layout {
+    let layouts: Value = serde_json::from_str(LAYOUTS).expect("golden layouts are not valid JSON");
+    let layout = &layouts[name];
+    assert!(layout.is_object(), "no golden layout for {}", name);
+
+    let fields = layout["fields"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{}: fields must be an array", name))
+        .iter()
+        .map(|field| Field {
+            name: field["name"].as_str().unwrap().to_string(),
+            offset: field["offset"].as_u64().unwrap() as usize,
+            size: field["size"].as_u64().unwrap() as usize,
+        })
+        .collect();
+    let vectors = layout["vectors"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{}: vectors must be an array", name))
+        .iter()
+        .map(|vector| Vector {
+            name: vector["name"].as_str().unwrap().to_string(),
+            bytes: decode_hex(vector["hex"].as_str().unwrap()),
+            values: vector["values"].as_object().unwrap().clone(),
+        })
+        .collect();
+
+    let layout = Layout {
+        name: name.to_string(),
+        len: layout["len"].as_u64().unwrap() as usize,
+        fields,
+        vectors,
+    };
+    for vector in &layout.vectors {
+        assert_eq!(
+            vector.bytes.len(),
+            layout.len,
+            "{} {:?}: vector length does not match the layout",
+            layout.name,
+            vector.name
+        );
+        for range in layout.padding() {
+            assert!(
+                vector.bytes[range.clone()].iter().all(|byte| *byte == 0),
+                "{} {:?}: padding {:?} must be zero",
+                layout.name,
+                vector.name,
+                range
+            );
+        }
+    }
+
+    layout
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    let digits: Vec<u8> = hex
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    assert!(
+        digits.len().is_multiple_of(2),
+        "odd number of hex digits: {}",
+        hex
+    );
+
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{:02x}", byte).unwrap();
+        hex
+    })
+}
+
+impl Layout {
+    pub fn field(&self, name: &str) -> &Field {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .unwrap_or_else(|| panic!("{} has no field {}", self.name, name))
+    }
+
+    /// The byte ranges not covered by any field
+    pub fn padding(&self) -> Vec<Range<usize>> {
+        let mut padding = Vec::new();
+        let mut offset = 0;
+        for field in &self.fields {
+            if field.offset > offset {
+                padding.push(offset..field.offset);
+            }
+            offset = offset.max(field.offset + field.size);
+        }
+        if self.len > offset {
+            padding.push(offset..self.len);
+        }
+        padding
+    }
+
+    /// Checks the `(name, offset, size)` of each field against the layout, e.g. from
+    /// `offset_of!` and `size_of`
+    pub fn assert_fields(&self, len: usize, fields: &[(&str, usize, usize)]) {
+        let expected: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| format!("{} {:?}", field.name, field.range()))
+            .collect();
+        let actual: Vec<String> = fields
+            .iter()
+            .map(|(name, offset, size)| format!("{} {:?}", name, *offset..offset + size))
+            .collect();
+
+        assert_eq!(
+            (self.len, expected),
+            (len, actual),
+            "{} layout drifted from golden/layouts.json, golden left, actual right",
+            self.name
+        );
+    }
+
+    /// Compares encoded bytes with a golden vector field by field, padding is only shown
+    pub fn assert_bytes_eq(&self, vector: &str, expected: &[u8], actual: &[u8]) {
+        let mut rows: Vec<(String, Range<usize>, bool)> = self
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.range(), false))
+            .chain(
+                self.padding()
+                    .into_iter()
+                    .map(|range| ("(padding)".to_string(), range, true)),
+            )
+            .collect();
+        rows.sort_by_key(|(_, range, _)| range.start);
+
+        let len_differs = expected.len() != actual.len();
+        let mut differs = len_differs;
+        let mut table = String::new();
+        for (name, range, ignored) in rows {
+            let expected = expected.get(range.clone()).map(encode_hex);
+            let actual = actual.get(range.clone()).map(encode_hex);
+            let marker = if ignored {
+                '~'
+            } else if expected != actual {
+                differs = true;
+                '*'
+            } else {
+                ' '
+            };
+            writeln!(
+                table,
+                "{} {:<22} {:>7}  {}\n  {:<22} {:>7}  {}",
+                marker,
+                name,
+                format!("{:?}", range),
+                expected.as_deref().unwrap_or("(missing)"),
+                "",
+                "",
+                actual.as_deref().unwrap_or("(missing)"),
+            )
+            .unwrap();
+        }
+
+        assert!(
+            !differs,
+            "{} {:?} does not match golden/layouts.json{}\n\
+             golden first, actual second, * differs, ~ padding\n{}",
+            self.name,
+            vector,
+            if len_differs {
+                format!(", {} bytes instead of {}", actual.len(), expected.len())
+            } else {
+                String::new()
+            },
+            table
+        );
+    }
+}
+
+impl Vector {
+    fn value(&self, field: &str) -> &Value {
+        self.values
+            .get(field)
+            .unwrap_or_else(|| panic!("{:?} has no value for {}", self.name, field))
+    }
+
+    pub fn u8(&self, field: &str) -> u8 {
+        self.u64(field).try_into().unwrap()
+    }
+
+    pub fn u64(&self, field: &str) -> u64 {
+        self.value(field).as_u64().unwrap()
+    }
+
+    pub fn option_u64(&self, field: &str) -> Option<u64> {
+        let value = self.value(field);
+        (!value.is_null()).then(|| value.as_u64().unwrap())
+    }
+
+    /// A base58 address
+    pub fn address(&self, field: &str) -> &str {
+        self.value(field).as_str().unwrap()
+    }
+
+    pub fn option_address(&self, field: &str) -> Option<&str> {
+        self.value(field).as_str()
+    }
+}
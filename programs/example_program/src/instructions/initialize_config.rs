@@ -1,18 +1,11 @@
 use crate::{
     accounts::config::Config,
-    config_seed_with_bump,
-    utils::{load_ix_data, load_signer, load_system_account, load_system_program, DataLen},
+    pda::Pda,
+    pod::{bytes_of, Pod, PodU64},
+    utils::{create_account, load_ix_data, DataLen},
+    validation::instruction_accounts,
 };
-use pinocchio::{
-    account_info::AccountInfo,
-    instruction::{Seed, Signer},
-    program_error::ProgramError,
-    pubkey::Pubkey,
-    sysvars::rent::Rent,
-    ProgramResult,
-};
-use pinocchio_log::log;
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, sysvars::rent::Rent, ProgramResult};
 
 use super::ExampleProgramInstructions;
 
@@ -21,20 +14,25 @@ use super::ExampleProgramInstructions;
 pub struct InitializeConfigIxData {
     pub discriminator: u8,
     pub config_bump: u8,
-    pub fees_bps: u64,
+    /// Where `fees_bps` was aligned to 8 before the `Pod` types, kept for existing clients
+    _padding: [u8; 6],
+    pub fees_bps: PodU64,
 }
 
+unsafe impl Pod for InitializeConfigIxData {}
+
 impl InitializeConfigIxData {
     pub fn new(config_bump: u8, fees_bps: u64) -> Self {
         Self {
             discriminator: ExampleProgramInstructions::InitializeConfig as u8,
             config_bump,
-            fees_bps,
+            _padding: [0; 6],
+            fees_bps: fees_bps.into(),
         }
     }
 
-    pub unsafe fn to_bytes(&self) -> &[u8] {
-        unsafe { crate::utils::to_bytes::<Self>(&self) }
+    pub fn to_bytes(&self) -> &[u8] {
+        bytes_of(self)
     }
 }
 
@@ -42,54 +40,123 @@ impl DataLen for InitializeConfigIxData {
     const LEN: usize = core::mem::size_of::<InitializeConfigIxData>();
 }
 
+instruction_accounts! {
+    /// The accounts of `InitializeConfig`
+    pub struct InitializeConfigAccounts(program_id, ix_data: &InitializeConfigIxData) {
+        /// The config PDA, derived from `base`
+        config,
+        /// The base key the config is derived from
+        base,
+        /// The admin of the new config, pays for the account
+        admin,
+        /// The rent sysvar
+        sysvar_rent,
+        /// The system program
+        system_program,
+    }
+
+    check system_program: [program = &pinocchio_system::ID];
+    check config: [owner = &pinocchio_system::ID, empty, writable];
+    check admin: [signer, writable];
+    check config: [pda = Config(base.key(), ix_data.config_bump)];
+}
+
 pub fn process_initilaize_config(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
-    let [config, base, admin, sysvar_rent, system_program] = accounts else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
-    let ix_data = unsafe { load_ix_data::<InitializeConfigIxData>(data)? };
+    let accounts = InitializeConfigAccounts::take(accounts)?;
+    let ix_data = load_ix_data::<InitializeConfigIxData>(data)?;
 
     // ----------------------- CHECKS -----------------------
-    load_system_program(system_program)?;
-    load_system_account(config, true)?;
-    load_signer(admin, true)?;
-
-    // Check PDA is correct
-    let pda = Config::create_program_address(program_id, &base.key(), ix_data.config_bump)?;
-    if config.key().ne(&pda) {
-        log!("Config account has an invalid key");
-        return Err(ProgramError::InvalidAccountData);
-    };
+    let InitializeConfigAccounts {
+        config,
+        base,
+        admin,
+        sysvar_rent,
+        ..
+    } = accounts.check(program_id, ix_data)?;
 
     // ----------------------- WORK -----------------------
 
     let rent = Rent::from_account_info(sysvar_rent)?;
 
     let bump_bytes = [ix_data.config_bump];
-    let seed_with_bump = config_seed_with_bump!(base.key(), &bump_bytes);
-    let signing_seeds = [
-        Seed::from(seed_with_bump[0]),
-        Seed::from(seed_with_bump[1]),
-        Seed::from(seed_with_bump[2]),
-    ];
-    Config::check_seeds(base.key(), ix_data.config_bump, &signing_seeds)?;
-    let signer = Signer::from(&signing_seeds);
-
-    CreateAccount {
-        from: admin,
-        to: config,
-        space: Config::LEN as u64,
-        owner: &crate::ID,
-        lamports: rent.minimum_balance(Config::LEN),
+    let signing_seeds = Config::signer_seeds(base.key(), &bump_bytes);
+
+    create_account(
+        admin,
+        config,
+        Config::LEN as u64,
+        program_id,
+        rent.minimum_balance(Config::LEN),
+        &signing_seeds,
+    )?;
+
+    Config::initialize(config, base.key(), admin.key(), ix_data)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::{offset_of, size_of};
+
+    use super::*;
+    use crate::golden::layout;
+
+    #[test]
+    fn test_initialize_config_ix_data_layout() {
+        layout("InitializeConfigIxData").assert_fields(
+            InitializeConfigIxData::LEN,
+            &[
+                (
+                    "discriminator",
+                    offset_of!(InitializeConfigIxData, discriminator),
+                    size_of::<u8>(),
+                ),
+                (
+                    "config_bump",
+                    offset_of!(InitializeConfigIxData, config_bump),
+                    size_of::<u8>(),
+                ),
+                (
+                    "fees_bps",
+                    offset_of!(InitializeConfigIxData, fees_bps),
+                    size_of::<PodU64>(),
+                ),
+            ],
+        );
     }
-    .invoke_signed(&[signer])?;
 
-    unsafe {
-        Config::initialize(config, &base.key(), &admin.key(), &ix_data)?;
+    #[test]
+    fn test_initialize_config_ix_data_golden_decode() {
+        for vector in layout("InitializeConfigIxData").vectors {
+            let ix_data = load_ix_data::<InitializeConfigIxData>(&vector.bytes);
+
+            assert_eq!(
+                InitializeConfigIxData {
+                    discriminator: vector.u8("discriminator"),
+                    config_bump: vector.u8("config_bump"),
+                    _padding: [0; 6],
+                    fees_bps: vector.u64("fees_bps").into(),
+                },
+                *ix_data.unwrap(),
+                "InitializeConfigIxData {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+        }
     }
 
-    Ok(())
+    #[test]
+    fn test_initialize_config_ix_data_golden_encode() {
+        let layout = layout("InitializeConfigIxData");
+        for vector in &layout.vectors {
+            let ix_data =
+                InitializeConfigIxData::new(vector.u8("config_bump"), vector.u64("fees_bps"));
+
+            layout.assert_bytes_eq(&vector.name, &vector.bytes, ix_data.to_bytes());
+        }
+    }
 }
@@ -3,9 +3,15 @@ pub mod update_config;
 
 use crate::errors::ExampleProgramError;
 
+/// Each instruction's accounts are declared in order by its `<Instruction>Accounts`, with the
+/// checks that make them signers or writable. This and those declarations are what the IDL is
+/// generated from.
 #[repr(u8)]
 pub enum ExampleProgramInstructions {
+    /// Creates and initializes the config PDA for `base`, see `InitializeConfigAccounts`
     InitializeConfig = 1,
+
+    /// Updates the admin and/or fees of an existing config, see `UpdateConfigAccounts`
     UpdateConfig = 2,
 }
 
@@ -1,10 +1,14 @@
 use crate::{
     accounts::config::Config,
-    utils::{load_ix_data, load_signer, DataLen},
+    errors::ExampleProgramError,
+    pod::{bytes_of, Pod, PodOption, PodU64},
+    utils::{load_ix_data, DataLen},
+    validation::instruction_accounts,
 };
 use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
+use pinocchio_log::log;
 
 use super::ExampleProgramInstructions;
 
@@ -12,21 +16,37 @@ use super::ExampleProgramInstructions;
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UpdateConfigIxData {
     pub discriminator: u8,
-    pub new_admin: Option<Pubkey>,
-    pub new_fees_bps: Option<u64>,
+    pub new_admin: PodOption<Pubkey>,
+    /// Where `new_fees_bps` was aligned to 8 before the `Pod` types, kept for existing clients
+    _padding: [u8; 6],
+    pub new_fees_bps: PodOption<PodU64, 7>,
 }
 
+unsafe impl Pod for UpdateConfigIxData {}
+
 impl UpdateConfigIxData {
     pub fn new(new_admin: Option<Pubkey>, new_fees_bps: Option<u64>) -> Self {
         Self {
             discriminator: ExampleProgramInstructions::UpdateConfig as u8,
-            new_admin,
-            new_fees_bps,
+            new_admin: new_admin.into(),
+            _padding: [0; 6],
+            new_fees_bps: new_fees_bps.map(PodU64::from).into(),
         }
     }
 
-    pub unsafe fn to_bytes(&self) -> &[u8] {
-        unsafe { crate::utils::to_bytes::<Self>(&self) }
+    pub fn to_bytes(&self) -> &[u8] {
+        bytes_of(self)
+    }
+
+    /// Loads the instruction data in place, rejecting `Option` tags other than 0 or 1
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        let ix_data = load_ix_data::<Self>(bytes)?;
+        if !ix_data.new_admin.is_valid() || !ix_data.new_fees_bps.is_valid() {
+            log!("Instruction data has an invalid option tag");
+            return Err(ExampleProgramError::InvalidInstructionData.into());
+        }
+
+        Ok(ix_data)
     }
 }
 
@@ -34,27 +54,131 @@ impl DataLen for UpdateConfigIxData {
     const LEN: usize = core::mem::size_of::<UpdateConfigIxData>();
 }
 
+instruction_accounts! {
+    /// The accounts of `UpdateConfig`
+    pub struct UpdateConfigAccounts(program_id) {
+        /// The config PDA to update
+        config,
+        /// The current admin of the config
+        admin,
+    }
+
+    check admin: [signer, writable];
+    check config: [owner = program_id, writable, initialized = Config, pda, has_one = admin];
+}
+
 pub fn process_update_config(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
-    let [config, admin] = accounts else {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    };
-    let ix_data = unsafe { load_ix_data::<UpdateConfigIxData>(data)? };
+    let accounts = UpdateConfigAccounts::take(accounts)?;
+    let ix_data = UpdateConfigIxData::load(data)?;
 
     // ----------------------- CHECKS -----------------------
-    load_signer(admin, true)?;
-    Config::load(program_id, &config, true, Some(admin))?;
-    unsafe {
-        Config::check_admin(config, admin)?;
-    }
+    let UpdateConfigAccounts { config, .. } = accounts.check(program_id)?;
 
     // ----------------------- WORK -----------------------
-    unsafe {
-        Config::update(config, &ix_data)?;
-    }
+    Config::update(config, ix_data)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use core::mem::{offset_of, size_of};
+    use std::str::FromStr;
+
+    use solana_pubkey::Pubkey as Address;
+
+    use super::*;
+    use crate::golden::{layout, Vector};
+
+    /// The bytes of a `PodOption<T>` before its value
+    fn payload_offset<T: Pod, const PAD: usize>() -> usize {
+        size_of::<PodOption<T, PAD>>() - size_of::<T>()
+    }
+
+    /// The `(discriminator, new_admin, new_fees_bps)` of a vector
+    fn golden_values(vector: &Vector) -> (u8, Option<Pubkey>, Option<u64>) {
+        (
+            vector.u8("discriminator"),
+            vector
+                .option_address("new_admin")
+                .map(|address| Address::from_str(address).unwrap().to_bytes()),
+            vector.option_u64("new_fees_bps"),
+        )
+    }
+
+    #[test]
+    fn test_update_config_ix_data_layout() {
+        let new_admin = offset_of!(UpdateConfigIxData, new_admin);
+        let new_fees_bps = offset_of!(UpdateConfigIxData, new_fees_bps);
+
+        layout("UpdateConfigIxData").assert_fields(
+            UpdateConfigIxData::LEN,
+            &[
+                (
+                    "discriminator",
+                    offset_of!(UpdateConfigIxData, discriminator),
+                    size_of::<u8>(),
+                ),
+                ("new_admin.is_some", new_admin, size_of::<u8>()),
+                (
+                    "new_admin",
+                    new_admin + payload_offset::<Pubkey, 0>(),
+                    size_of::<Pubkey>(),
+                ),
+                ("new_fees_bps.is_some", new_fees_bps, size_of::<u8>()),
+                (
+                    "new_fees_bps",
+                    new_fees_bps + payload_offset::<PodU64, 7>(),
+                    size_of::<PodU64>(),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_update_config_ix_data_golden_decode() {
+        for vector in layout("UpdateConfigIxData").vectors {
+            let ix_data = UpdateConfigIxData::load(&vector.bytes).unwrap();
+
+            assert_eq!(
+                golden_values(&vector),
+                (
+                    ix_data.discriminator,
+                    ix_data.new_admin.get(),
+                    ix_data.new_fees_bps.get().map(u64::from)
+                ),
+                "UpdateConfigIxData {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_config_ix_data_golden_encode() {
+        let layout = layout("UpdateConfigIxData");
+        for vector in &layout.vectors {
+            let (_, new_admin, new_fees_bps) = golden_values(vector);
+            let ix_data = UpdateConfigIxData::new(new_admin, new_fees_bps);
+
+            layout.assert_bytes_eq(&vector.name, &vector.bytes, ix_data.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_update_config_ix_data_invalid_option_tag_fails() {
+        let vector = &layout("UpdateConfigIxData").vectors[0];
+        for tag in ["new_admin.is_some", "new_fees_bps.is_some"] {
+            let mut bytes = vector.bytes.clone();
+            bytes[layout("UpdateConfigIxData").field(tag).offset] = 2;
+
+            assert_eq!(
+                UpdateConfigIxData::load(&bytes),
+                Err(ExampleProgramError::InvalidInstructionData.into())
+            );
+        }
+    }
+}
@@ -9,8 +9,15 @@ use pinocchio_log::log;
 pub mod accounts;
 pub mod errors;
 pub mod instructions;
+pub mod pda;
+pub mod pod;
 pub mod utils;
+mod validation;
 
+#[cfg(test)]
+mod golden;
+
+use errors::ExampleProgramError;
 use instructions::{
     initialize_config::process_initilaize_config, update_config::process_update_config,
     ExampleProgramInstructions,
@@ -25,12 +32,15 @@ no_allocator!();
 default_panic_handler!();
 
 #[inline(always)]
-fn process_instruction(
+pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let discriminator = ExampleProgramInstructions::try_from(&instruction_data[0])?;
+    let Some(discriminator) = instruction_data.first() else {
+        return Err(ExampleProgramError::InvalidInstructionData.into());
+    };
+    let discriminator = ExampleProgramInstructions::try_from(discriminator)?;
 
     match discriminator {
         ExampleProgramInstructions::InitializeConfig => {
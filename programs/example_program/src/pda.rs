@@ -0,0 +1,89 @@
+//! Program derived addresses, shared by the program and the SDK so both derive an account's
+//! address from the same seeds.
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+};
+use pinocchio_log::log;
+
+use crate::utils::{create_program_address, find_program_address};
+
+/// An account whose address is a PDA of `N` seeds, the last one being its bump
+///
+/// Implementors only list their seeds, e.g. `Config` derives from its base:
+///
+/// ```ignore
+/// impl Pda<3> for Config {
+///     const NAME: &'static str = "Config";
+///     type Key = Pubkey;
+///
+///     fn seeds<'a>(base: &'a Pubkey, bump: &'a [u8; 1]) -> [&'a [u8]; 3] {
+///         [Self::SEED, base, bump]
+///     }
+/// }
+/// ```
+pub trait Pda<const N: usize> {
+    /// The account's name in logs
+    const NAME: &'static str;
+
+    /// What the address is derived from besides the program id and the bump
+    type Key: ?Sized;
+
+    /// The address's seeds, ending with `bump`
+    fn seeds<'a>(key: &'a Self::Key, bump: &'a [u8; 1]) -> [&'a [u8]; N];
+
+    /// Finds the address and its canonical bump
+    fn find(key: &Self::Key, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = Self::seeds(key, &[0]);
+        find_program_address(&seeds[..N - 1], program_id)
+    }
+
+    /// Derives the address from a known bump, failing if the seeds land on the curve
+    fn create_with_bump(
+        key: &Self::Key,
+        bump: u8,
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, ProgramError> {
+        create_program_address(&Self::seeds(key, &[bump]), program_id)
+    }
+
+    /// The seeds to sign a CPI as the account, e.g. to create it
+    fn signer_seeds<'a>(key: &'a Self::Key, bump: &'a [u8; 1]) -> [Seed<'a>; N] {
+        Self::seeds(key, bump).map(Seed::from)
+    }
+
+    /// Checks that `account` is at the address derived from `key` and `bump`
+    fn verify(
+        account: &AccountInfo,
+        key: &Self::Key,
+        bump: u8,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let address = Self::create_with_bump(key, bump, program_id)?;
+        if account.key().ne(&address) {
+            log!("{} account has an invalid key", Self::NAME);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
+
+/// A `Pda` whose key and bump are stored in its own data, so a loaded account can check its
+/// address without them being passed in
+pub trait PdaAccount<const N: usize>: Pda<N> {
+    /// The key the address is derived from
+    fn pda_key(&self) -> &Self::Key;
+
+    /// The address's bump
+    fn pda_bump(&self) -> u8;
+
+    /// Checks that `account` is at the address derived from this data
+    fn verify_address(
+        &self,
+        account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        Self::verify(account, self.pda_key(), self.pda_bump(), program_id)
+    }
+}
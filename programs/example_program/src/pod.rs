@@ -0,0 +1,259 @@
+//! Plain old data: types that can be read from and written to raw account or instruction bytes
+//! in place, without `unsafe` at the call site.
+//!
+//! Account and instruction data carry no alignment guarantees once copied off chain, so every
+//! `Pod` type has an alignment of 1. Multi-byte integers are stored as little-endian byte arrays,
+//! see `PodU64`.
+
+use core::mem::{align_of, size_of};
+
+use pinocchio::program_error::ProgramError;
+
+/// A type that is valid for any bit pattern and has no padding
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` or `#[repr(transparent)]` and only contain `Pod` fields.
+/// With every field aligned to 1 such a type has no padding, which the functions below check at
+/// compile time.
+pub unsafe trait Pod: Copy + 'static {}
+
+const fn assert_aligned<T: Pod>() {
+    assert!(
+        align_of::<T>() == 1,
+        "Pod types must have an alignment of 1"
+    );
+}
+
+unsafe impl Pod for u8 {}
+unsafe impl<const N: usize> Pod for [u8; N] {}
+
+/// Reads `bytes` as a `T` in place, failing if they are not exactly `size_of::<T>()` long
+#[inline(always)]
+pub fn load<T: Pod>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    const { assert_aligned::<T>() };
+    if bytes.len() != size_of::<T>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // SAFETY: the length matches, `T` has an alignment of 1 and is valid for any bytes
+    Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+}
+
+/// Reads `bytes` as a mutable `T` in place, failing if they are not exactly `size_of::<T>()`
+/// long
+#[inline(always)]
+pub fn load_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    const { assert_aligned::<T>() };
+    if bytes.len() != size_of::<T>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // SAFETY: as in `load`, and any bytes written leave a valid `T`
+    Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut T) })
+}
+
+/// The bytes of `data`, which are all initialized since `T` has no padding
+#[inline(always)]
+pub fn bytes_of<T: Pod>(data: &T) -> &[u8] {
+    const { assert_aligned::<T>() };
+    // SAFETY: `T` has no padding, so all `size_of::<T>()` bytes are initialized
+    unsafe { core::slice::from_raw_parts(data as *const T as *const u8, size_of::<T>()) }
+}
+
+/// The mutable bytes of `data`, any bytes written leave a valid `T`
+#[inline(always)]
+pub fn bytes_of_mut<T: Pod>(data: &mut T) -> &mut [u8] {
+    const { assert_aligned::<T>() };
+    // SAFETY: as in `bytes_of`, and `T` is valid for any bytes
+    unsafe { core::slice::from_raw_parts_mut(data as *mut T as *mut u8, size_of::<T>()) }
+}
+
+/// A little-endian `u64` with an alignment of 1
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PodU64([u8; 8]);
+
+unsafe impl Pod for PodU64 {}
+
+impl PodU64 {
+    pub const fn new(value: u64) -> Self {
+        Self(value.to_le_bytes())
+    }
+
+    pub const fn get(self) -> u64 {
+        u64::from_le_bytes(self.0)
+    }
+}
+
+impl From<u64> for PodU64 {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<PodU64> for u64 {
+    fn from(value: PodU64) -> Self {
+        value.get()
+    }
+}
+
+/// An `Option<T>` as a one byte tag, 0 for `None` and 1 for `Some`, followed by `PAD` reserved
+/// bytes and the value. The reserved bytes and the value of a `None` are written as zeroes and
+/// ignored when read.
+///
+/// `PAD` keeps the value where a `#[repr(C)]` struct holding a plain `Option<T>` put it, e.g. 7
+/// for an `Option<u64>`, so data written before the `Pod` types still loads.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct PodOption<T: Pod, const PAD: usize = 0> {
+    tag: u8,
+    _padding: [u8; PAD],
+    value: T,
+}
+
+unsafe impl<T: Pod, const PAD: usize> Pod for PodOption<T, PAD> {}
+
+impl<T: Pod + Default, const PAD: usize> PodOption<T, PAD> {
+    pub fn some(value: T) -> Self {
+        Self {
+            tag: 1,
+            _padding: [0; PAD],
+            value,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self {
+            tag: 0,
+            _padding: [0; PAD],
+            value: T::default(),
+        }
+    }
+}
+
+impl<T: Pod, const PAD: usize> PodOption<T, PAD> {
+    /// Whether the tag is 0 or 1, loaded data must be checked before `get`
+    pub fn is_valid(&self) -> bool {
+        self.tag <= 1
+    }
+
+    /// The value if the tag is 1, any other tag reads as `None`
+    pub fn get(&self) -> Option<T> {
+        (self.tag == 1).then_some(self.value)
+    }
+}
+
+impl<T: Pod + Default, const PAD: usize> Default for PodOption<T, PAD> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T: Pod + Default, const PAD: usize> From<Option<T>> for PodOption<T, PAD> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or_else(Self::none, Self::some)
+    }
+}
+
+impl<T: Pod + PartialEq, const PAD: usize> PartialEq for PodOption<T, PAD> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: Pod + Eq, const PAD: usize> Eq for PodOption<T, PAD> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Packed {
+        tag: u8,
+        value: PodU64,
+        option: PodOption<[u8; 2]>,
+    }
+
+    unsafe impl Pod for Packed {}
+
+    #[test]
+    fn test_pod_types_have_no_padding() {
+        assert_eq!(size_of::<PodU64>(), 8);
+        assert_eq!(size_of::<PodOption<PodU64>>(), 9);
+        assert_eq!(size_of::<PodOption<PodU64, 7>>(), size_of::<Option<u64>>());
+        assert_eq!(size_of::<Packed>(), 1 + 8 + 3);
+        assert_eq!(align_of::<Packed>(), 1);
+    }
+
+    #[test]
+    fn test_load_unaligned() {
+        let bytes = [0xff, 7, 1, 2, 3, 4, 5, 6, 7, 8, 1, 9, 9];
+
+        // One byte past an aligned buffer's start
+        let packed = load::<Packed>(&bytes[1..]).unwrap();
+
+        assert_eq!(packed.tag, 7);
+        assert_eq!(packed.value.get(), 0x0807060504030201);
+        assert_eq!(packed.option.get(), Some([9, 9]));
+        assert_eq!(bytes_of(packed), &bytes[1..]);
+    }
+
+    #[test]
+    fn test_load_wrong_length_fails() {
+        let bytes = [0; 13];
+
+        assert_eq!(
+            load::<Packed>(&bytes[..11]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            load::<Packed>(&bytes),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_load_mut_writes_in_place() {
+        let mut bytes = [0; 12];
+
+        let packed = load_mut::<Packed>(&mut bytes).unwrap();
+        packed.value = PodU64::new(0x0102);
+        packed.option = Some([3, 4]).into();
+
+        assert_eq!(bytes, [0, 2, 1, 0, 0, 0, 0, 0, 0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_pod_option() {
+        let none = PodOption::<PodU64>::none();
+        assert_eq!(bytes_of(&none), &[0; 9]);
+        assert_eq!(none.get(), None);
+
+        let some = PodOption::<PodU64>::from(Some(PodU64::new(5)));
+        assert_eq!(bytes_of(&some), &[1, 5, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(some.get(), Some(PodU64::new(5)));
+
+        // The value of a `None` is ignored, an invalid tag is flagged
+        let mut bytes = [0, 5, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(*load::<PodOption<PodU64>>(&bytes).unwrap(), none);
+        bytes[0] = 2;
+        let invalid = load::<PodOption<PodU64>>(&bytes).unwrap();
+        assert!(!invalid.is_valid());
+        assert_eq!(invalid.get(), None);
+    }
+
+    #[test]
+    fn test_padded_pod_option() {
+        let some = PodOption::<PodU64, 7>::some(PodU64::new(5));
+        assert_eq!(
+            bytes_of(&some),
+            &[1, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        // Reserved bytes are ignored when read
+        let bytes = [
+            1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 5, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(*load::<PodOption<PodU64, 7>>(&bytes).unwrap(), some);
+    }
+}
@@ -1,7 +1,12 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
-use pinocchio_log::log;
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
 
-use crate::errors::ExampleProgramError;
+use crate::{
+    errors::ExampleProgramError,
+    pod::{load, load_mut, Pod},
+};
 
 pub trait DataLen {
     const LEN: usize;
@@ -11,8 +16,9 @@ pub trait Initialized {
     fn is_initialized(&self) -> bool;
 }
 
+/// Loads an initialized account of type `T` in place
 #[inline(always)]
-pub unsafe fn load_account<T: DataLen + Initialized>(bytes: &[u8]) -> Result<&T, ProgramError> {
+pub fn load_account<T: Pod + Initialized>(bytes: &[u8]) -> Result<&T, ProgramError> {
     load_account_unchecked::<T>(bytes).and_then(|account| {
         if account.is_initialized() {
             Ok(account)
@@ -22,18 +28,14 @@ pub unsafe fn load_account<T: DataLen + Initialized>(bytes: &[u8]) -> Result<&T,
     })
 }
 
+/// Loads an account of type `T` in place, initialized or not
 #[inline(always)]
-pub unsafe fn load_account_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
-    if bytes.len() != T::LEN {
-        return Err(ProgramError::InvalidAccountData);
-    }
-    Ok(&*(bytes.as_ptr() as *const T))
+pub fn load_account_unchecked<T: Pod>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    load::<T>(bytes)
 }
 
 #[inline(always)]
-pub unsafe fn load_account_mut<T: DataLen + Initialized>(
-    bytes: &mut [u8],
-) -> Result<&mut T, ProgramError> {
+pub fn load_account_mut<T: Pod + Initialized>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
     load_account_mut_unchecked::<T>(bytes).and_then(|acc| {
         if acc.is_initialized() {
             Ok(acc)
@@ -44,69 +46,153 @@ pub unsafe fn load_account_mut<T: DataLen + Initialized>(
 }
 
 #[inline(always)]
-pub unsafe fn load_account_mut_unchecked<T: DataLen>(
-    bytes: &mut [u8],
-) -> Result<&mut T, ProgramError> {
-    if bytes.len() != T::LEN {
-        return Err(ProgramError::InvalidAccountData);
-    }
-    Ok(&mut *(bytes.as_mut_ptr() as *mut T))
+pub fn load_account_mut_unchecked<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    load_mut::<T>(bytes)
 }
 
 #[inline(always)]
-pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
-    if bytes.len() != T::LEN {
-        return Err(ExampleProgramError::InvalidInstructionData.into());
-    }
-    Ok(&*(bytes.as_ptr() as *const T))
+pub fn load_ix_data<T: Pod>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    load::<T>(bytes).map_err(|_| ExampleProgramError::InvalidInstructionData.into())
 }
 
-pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
-    core::slice::from_raw_parts(data as *const T as *const u8, T::LEN)
-}
+/// Derives a PDA with the `sol_create_program_address` syscall. Off-chain, where pinocchio
+/// panics instead, the runtime's derivation runs on the host so `process_instruction` can be
+/// called natively.
+pub fn create_program_address(
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<Pubkey, ProgramError> {
+    #[cfg(target_os = "solana")]
+    {
+        pinocchio::pubkey::create_program_address(seeds, program_id)
+    }
 
-pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
-    core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
+    #[cfg(not(target_os = "solana"))]
+    {
+        // The syscall returns 1 for seeds that land on the curve, which pinocchio turns into
+        // `Custom(1)`
+        solana_pubkey::Pubkey::create_program_address(
+            seeds,
+            &solana_pubkey::Pubkey::new_from_array(*program_id),
+        )
+        .map(|pda| pda.to_bytes())
+        .map_err(|_| ProgramError::from(1u64))
+    }
 }
 
-pub fn load_signer(info: &AccountInfo, expect_writable: bool) -> Result<(), ProgramError> {
-    if !info.is_signer() {
-        log!("Account is not a signer");
-        return Err(ProgramError::MissingRequiredSignature);
+/// Finds a PDA and its bump with the `sol_try_find_program_address` syscall, or on the host
+/// like `create_program_address`
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    #[cfg(target_os = "solana")]
+    {
+        pinocchio::pubkey::find_program_address(seeds, program_id)
     }
-    if expect_writable && !info.is_writable() {
-        log!("Signer is not writable");
-        return Err(ProgramError::InvalidAccountData);
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let (pda, bump) = solana_pubkey::Pubkey::find_program_address(
+            seeds,
+            &solana_pubkey::Pubkey::new_from_array(*program_id),
+        );
+        (pda.to_bytes(), bump)
     }
+}
+
+/// Runs the system program's `CreateAccount` for `create_account` off-chain, with the same
+/// arguments and `to`'s signer seeds as plain slices
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+pub type HostCreateAccount =
+    fn(&AccountInfo, &AccountInfo, u64, &Pubkey, u64, &[&[u8]]) -> ProgramResult;
 
-    Ok(())
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+std::thread_local! {
+    static HOST_CREATE_ACCOUNT: core::cell::Cell<Option<HostCreateAccount>> =
+        const { core::cell::Cell::new(None) };
 }
 
-pub fn load_system_program(info: &AccountInfo) -> Result<(), ProgramError> {
-    if info.key().ne(&pinocchio_system::id()) {
-        log!("Account is not the system program");
-        return Err(ProgramError::IncorrectProgramId);
+/// Runs `f` with `create_account` going through `host_create_account` instead of applying the
+/// system program's effects directly, for hosts that can run the real system program such as a
+/// `ProgramTest` builtin
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+pub fn with_host_create_account<R>(
+    host_create_account: HostCreateAccount,
+    f: impl FnOnce() -> R,
+) -> R {
+    struct Restore(Option<HostCreateAccount>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            HOST_CREATE_ACCOUNT.set(self.0);
+        }
     }
 
-    Ok(())
+    let _restore = Restore(HOST_CREATE_ACCOUNT.replace(Some(host_create_account)));
+    f()
 }
 
-pub fn load_system_account(info: &AccountInfo, is_writable: bool) -> Result<(), ProgramError> {
-    let owner = unsafe { info.owner() };
-    if owner.ne(&pinocchio_system::id()) {
-        log!("Account is not owned by the system program");
-        return Err(ProgramError::InvalidAccountOwner);
+/// Creates `to`, a PDA of `owner` signed for with `signer_seeds`, with a CPI to the system
+/// program. Off-chain, where pinocchio skips CPIs, the runtime's and the system program's checks
+/// and effects are applied to the accounts directly, unless the `host` feature's
+/// `with_host_create_account` provides a system program.
+pub fn create_account(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+    lamports: u64,
+    signer_seeds: &[Seed],
+) -> ProgramResult {
+    #[cfg(target_os = "solana")]
+    {
+        pinocchio_system::instructions::CreateAccount {
+            from,
+            to,
+            space,
+            owner,
+            lamports,
+        }
+        .invoke_signed(&[pinocchio::instruction::Signer::from(signer_seeds)])
     }
 
-    if !info.data_is_empty() {
-        log!("Account data is not empty");
-        return Err(ProgramError::AccountAlreadyInitialized);
-    }
+    #[cfg(not(target_os = "solana"))]
+    {
+        let seeds: Vec<&[u8]> = signer_seeds.iter().map(|seed| &**seed).collect();
 
-    if is_writable && !info.is_writable() {
-        log!("Account is not writable");
-        return Err(ProgramError::InvalidAccountData);
-    }
+        #[cfg(feature = "host")]
+        if let Some(host_create_account) = HOST_CREATE_ACCOUNT.get() {
+            return host_create_account(from, to, space, owner, lamports, &seeds);
+        }
+
+        // The runtime only lets `to` sign if the seeds derive it from the calling program. It
+        // rejects the CPI with `PrivilegeEscalation`, which `ProgramError` cannot express, so
+        // this returns what the system program returns for an unsigned `to`.
+        if !to.is_signer() && create_program_address(&seeds, owner).ok().as_ref() != Some(to.key())
+        {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // `SystemError::AccountAlreadyInUse`
+        if to.lamports() > 0 || !to.data_is_empty() || !to.is_owned_by(&pinocchio_system::id()) {
+            return Err(ProgramError::Custom(0));
+        }
+        if !from.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if !from.data_is_empty() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // `SystemError::ResultWithNegativeLamports`
+        if from.lamports() < lamports {
+            return Err(ProgramError::Custom(1));
+        }
 
-    Ok(())
+        to.realloc(space as usize, true)?;
+        unsafe {
+            to.assign(owner);
+        }
+        *from.try_borrow_mut_lamports()? -= lamports;
+        *to.try_borrow_mut_lamports()? += lamports;
+
+        Ok(())
+    }
 }
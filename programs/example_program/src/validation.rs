@@ -0,0 +1,264 @@
+//! Declarative account validation for instruction processors.
+//!
+//! `instruction_accounts!` declares an instruction's accounts in order, then the constraints they
+//! must meet in the order they are checked, and generates a struct of `&AccountInfo`s with the
+//! checks. The checks are the ones a processor would write by hand, inlined, with no allocation.
+//!
+//! ```ignore
+//! instruction_accounts! {
+//!     /// The accounts of `UpdateConfig`
+//!     pub struct UpdateConfigAccounts(program_id) {
+//!         config,
+//!         admin,
+//!     }
+//!
+//!     check admin: [signer, writable];
+//!     check config: [owner = program_id, writable, initialized = Config, pda, has_one = admin];
+//! }
+//!
+//! let accounts = UpdateConfigAccounts::take(accounts)?;
+//! let ix_data = UpdateConfigIxData::load(data)?;
+//! let UpdateConfigAccounts { config, admin } = accounts.check(program_id)?;
+//! ```
+//!
+//! `take` only checks the number of accounts, so instruction data can be loaded before the
+//! constraints run, and `load` does both at once. The parenthesized list names the program id,
+//! followed by any extra arguments of `check` the constraints refer to, e.g.
+//! `ix_data: &InitializeConfigIxData`.
+//!
+//! Each `check` runs its constraints left to right and may refer to any account by name. An
+//! account can be checked more than once, e.g. once before and once after another account:
+//!
+//! - `signer`: the account signed the transaction
+//! - `writable`: the account is writable
+//! - `owner = <&Pubkey>`: the account is owned by that program
+//! - `program = <&Pubkey>`: the account is that program
+//! - `empty`: the account has no data
+//! - `pda = <Type>(<key>, <bump>)`: the account is at `Type`'s `Pda` derived from `key` and `bump`
+//! - `initialized = <Type>`: the account's data is an initialized `Type`, which the constraints
+//!   after it in the same `check` use
+//! - `pda`: after `initialized`, the account is at the `Pda` derived from its own data, see
+//!   `PdaAccount`
+//! - `has_one = <account>`: after `initialized`, the data's `<account>()` is that account's key
+//!
+//! Failed checks log which account broke which constraint.
+
+/// Accounts taken from the instruction in order whose constraints are not checked yet
+pub struct Unchecked<T>(T);
+
+/// Declares an instruction's accounts and their constraints, see the module docs
+macro_rules! instruction_accounts {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($program_id:ident $(, $arg:ident: $arg_ty:ty)* $(,)?) {
+            $(
+                $(#[$account_meta:meta])*
+                $account:ident
+            ),* $(,)?
+        }
+
+        $(check $checked:ident: [$($constraints:tt)*];)*
+    ) => {
+        $(#[$meta])*
+        $vis struct $name<'a> {
+            $(
+                $(#[$account_meta])*
+                pub $account: &'a pinocchio::account_info::AccountInfo,
+            )*
+        }
+
+        impl<'a> $name<'a> {
+            /// Takes the accounts in order, `check` checks their constraints
+            #[inline(always)]
+            pub fn take(
+                accounts: &'a [pinocchio::account_info::AccountInfo],
+            ) -> Result<
+                $crate::validation::Unchecked<Self>,
+                pinocchio::program_error::ProgramError,
+            > {
+                let [$($account),*] = accounts else {
+                    return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+                };
+
+                Ok($crate::validation::Unchecked::new(Self { $($account),* }))
+            }
+
+            /// Takes the accounts in order and checks their constraints
+            #[inline(always)]
+            pub fn load(
+                $program_id: &pinocchio::pubkey::Pubkey,
+                accounts: &'a [pinocchio::account_info::AccountInfo],
+                $($arg: $arg_ty,)*
+            ) -> Result<Self, pinocchio::program_error::ProgramError> {
+                Self::take(accounts)?.check($program_id, $($arg,)*)
+            }
+        }
+
+        impl<'a> $crate::validation::Unchecked<$name<'a>> {
+            /// Checks the accounts' constraints in the order they are declared
+            #[inline(always)]
+            pub fn check(
+                self,
+                $program_id: &pinocchio::pubkey::Pubkey,
+                $($arg: $arg_ty,)*
+            ) -> Result<$name<'a>, pinocchio::program_error::ProgramError> {
+                let $name { $($account),* } = self.into_inner();
+                let _ = ($program_id, $($account,)* $($arg,)*);
+
+                $(
+                    {
+                        $crate::validation::account_constraints!(
+                            $checked, $program_id, [];
+                            $($constraints)*
+                        );
+                    }
+                )*
+
+                Ok($name { $($account),* })
+            }
+        }
+    };
+}
+
+/// Expands one account's constraints into checks, one constraint at a time. `[$data]` is the
+/// account's data once `initialized` loaded it.
+macro_rules! account_constraints {
+    ($account:ident, $program_id:ident, [$($data:ident)?];) => {};
+
+    ($account:ident, $program_id:ident, [$($data:ident)?]; signer $(, $($rest:tt)*)?) => {
+        if !$account.is_signer() {
+            pinocchio_log::log!("{} account is not a signer", stringify!($account));
+            return Err(pinocchio::program_error::ProgramError::MissingRequiredSignature);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    ($account:ident, $program_id:ident, [$($data:ident)?]; writable $(, $($rest:tt)*)?) => {
+        if !$account.is_writable() {
+            pinocchio_log::log!("{} account is not writable", stringify!($account));
+            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    (
+        $account:ident, $program_id:ident, [$($data:ident)?];
+        owner = $owner:expr $(, $($rest:tt)*)?
+    ) => {
+        if !$account.is_owned_by($owner) {
+            pinocchio_log::log!("{} account has an invalid owner", stringify!($account));
+            return Err(pinocchio::program_error::ProgramError::InvalidAccountOwner);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    (
+        $account:ident, $program_id:ident, [$($data:ident)?];
+        program = $program:expr $(, $($rest:tt)*)?
+    ) => {
+        if $account.key().ne($program) {
+            pinocchio_log::log!("{} account is not the expected program", stringify!($account));
+            return Err(pinocchio::program_error::ProgramError::IncorrectProgramId);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    ($account:ident, $program_id:ident, [$($data:ident)?]; empty $(, $($rest:tt)*)?) => {
+        if !$account.data_is_empty() {
+            pinocchio_log::log!("{} account data is not empty", stringify!($account));
+            return Err(pinocchio::program_error::ProgramError::AccountAlreadyInitialized);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    (
+        $account:ident, $program_id:ident, [$($data:ident)?];
+        pda = $pda:ident($key:expr, $bump:expr) $(, $($rest:tt)*)?
+    ) => {
+        {
+            use $crate::pda::Pda as _;
+            $pda::verify($account, $key, $bump, $program_id)?;
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$($data)?]; $($($rest)*)?
+        );
+    };
+
+    (
+        $account:ident, $program_id:ident, [];
+        initialized = $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        let data = $account.try_borrow_data()?;
+        let account_data = match $crate::utils::load_account::<$ty>(&data) {
+            Ok(account_data) => account_data,
+            Err(error) => {
+                pinocchio_log::log!("{} account could not be deserialized", stringify!($account));
+                return Err(error);
+            }
+        };
+        $crate::validation::account_constraints!(
+            $account, $program_id, [account_data]; $($($rest)*)?
+        );
+    };
+
+    ($account:ident, $program_id:ident, [$data:ident]; pda $(, $($rest:tt)*)?) => {
+        {
+            use $crate::pda::PdaAccount as _;
+            $data.verify_address($account, $program_id)?;
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$data]; $($($rest)*)?
+        );
+    };
+
+    (
+        $account:ident, $program_id:ident, [$data:ident];
+        has_one = $other:ident $(, $($rest:tt)*)?
+    ) => {
+        if $data.$other().ne($other.key()) {
+            pinocchio_log::log!(
+                "{} account has an invalid {}",
+                stringify!($account),
+                stringify!($other)
+            );
+            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+        }
+        $crate::validation::account_constraints!(
+            $account, $program_id, [$data]; $($($rest)*)?
+        );
+    };
+
+    ($account:ident, $program_id:ident, []; $constraint:ident $($rest:tt)*) => {
+        compile_error!(concat!(
+            "`",
+            stringify!($constraint),
+            "` on `",
+            stringify!($account),
+            "` needs `initialized = <Type>` before it"
+        ));
+    };
+}
+
+impl<T> Unchecked<T> {
+    /// Only for `instruction_accounts!`, which checks the accounts before handing them out
+    pub(crate) fn new(accounts: T) -> Self {
+        Self(accounts)
+    }
+
+    pub(crate) fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+pub(crate) use account_constraints;
+pub(crate) use instruction_accounts;
@@ -0,0 +1,14 @@
+//! With the `idl` feature, generates the example program IDL from the program sources, see
+//! `build/idl.rs`. The SDK itself uses the committed `idl/example_program.json`, so a default build
+//! needs neither the program sources nor the generator's dependencies.
+
+#[cfg(feature = "idl")]
+#[path = "build/idl.rs"]
+mod idl;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "idl")]
+    idl::generate();
+}
@@ -0,0 +1,500 @@
+//! Generates the example program IDL from the program's Rust sources, with the `idl` feature.
+//!
+//! Instructions come from `ExampleProgramInstructions`, their accounts from the matching
+//! `<Instruction>Accounts` declared with `instruction_accounts!`, args from the matching
+//! `<Instruction>IxData` structs, accounts from `ExampleProgramDiscriminator` and the structs in
+//! `accounts/`, and errors from `ExampleProgramError`. Types are described with their bytemuck
+//! layout, so `PodOption` is a generic struct with a tag byte and an always present value.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Map, Value};
+use syn::{
+    braced, bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Expr, Fields, GenericArgument, GenericParam, Ident, Item, ItemEnum, ItemStruct, Lit,
+    PathArguments, Token, Type, Visibility,
+};
+
+const PROGRAM_NAME: &str = "example_program";
+
+pub fn generate() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let program_dir = manifest_dir.join("../programs/example_program");
+    let src_dir = program_dir.join("src");
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let items = ProgramItems::parse(&src_dir);
+    let idl = json!({
+        "address": parse_program_id(&src_dir.join("lib.rs")),
+        "metadata": {
+            "name": PROGRAM_NAME,
+            "version": env::var("CARGO_PKG_VERSION").unwrap(),
+            "spec": "0.1.0",
+        },
+        "instructions": items.instructions(),
+        "accounts": items.accounts(),
+        "errors": items.errors(),
+        "types": items.types(),
+    });
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(
+        out_dir.join("example_program_idl.json"),
+        serde_json::to_string_pretty(&idl).unwrap() + "\n",
+    )
+    .unwrap();
+}
+
+struct ProgramItems {
+    instructions: ItemEnum,
+    instruction_accounts: Vec<InstructionAccounts>,
+    discriminators: ItemEnum,
+    errors: ItemEnum,
+    ix_data: Vec<ItemStruct>,
+    accounts: Vec<ItemStruct>,
+    pod_option: ItemStruct,
+}
+
+impl ProgramItems {
+    fn parse(src_dir: &Path) -> Self {
+        let instruction_items = parse_dir(&src_dir.join("instructions"));
+        let account_items = parse_dir(&src_dir.join("accounts"));
+        let error_items = parse_file(&src_dir.join("errors.rs"));
+        let pod_items = parse_file(&src_dir.join("pod.rs"));
+
+        Self {
+            instructions: find_enum(&instruction_items, "ExampleProgramInstructions"),
+            instruction_accounts: instruction_accounts(&instruction_items),
+            discriminators: find_enum(&account_items, "ExampleProgramDiscriminator"),
+            errors: find_enum(&error_items, "ExampleProgramError"),
+            ix_data: structs(&instruction_items),
+            accounts: structs(&account_items),
+            pod_option: structs(&pod_items)
+                .into_iter()
+                .find(|item| item.ident == "PodOption")
+                .expect("struct PodOption not found"),
+        }
+    }
+
+    fn instructions(&self) -> Vec<Value> {
+        enum_values(&self.instructions)
+            .into_iter()
+            .map(|(variant, discriminator)| {
+                let name = variant.ident.to_string();
+
+                let accounts_name = format!("{}Accounts", name);
+                let accounts = self
+                    .instruction_accounts
+                    .iter()
+                    .find(|accounts| accounts.name == accounts_name)
+                    .unwrap_or_else(|| panic!("{} has no {} declaration", name, accounts_name));
+
+                let ix_data_name = format!("{}IxData", name);
+                let ix_data = self
+                    .ix_data
+                    .iter()
+                    .find(|item| item.ident == ix_data_name)
+                    .unwrap_or_else(|| panic!("{} has no {} struct", name, ix_data_name));
+
+                json!({
+                    "name": to_snake_case(&name),
+                    "docs": docs(&variant.attrs),
+                    "discriminator": [discriminator],
+                    "accounts": accounts.idl_accounts(),
+                    "args": self.fields(ix_data)
+                        .into_iter()
+                        .filter(|field| field["name"] != "discriminator")
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect()
+    }
+
+    fn accounts(&self) -> Vec<Value> {
+        enum_values(&self.discriminators)
+            .into_iter()
+            .map(|(variant, discriminator)| {
+                let name = variant.ident.to_string();
+                assert!(
+                    self.accounts.iter().any(|item| item.ident == name),
+                    "account discriminator {} has no matching struct",
+                    name
+                );
+
+                json!({ "name": name, "discriminator": [discriminator] })
+            })
+            .collect()
+    }
+
+    fn errors(&self) -> Vec<Value> {
+        enum_values(&self.errors)
+            .into_iter()
+            .map(|(variant, code)| {
+                let mut error = Map::new();
+                error.insert("code".to_string(), json!(code));
+                error.insert("name".to_string(), json!(variant.ident.to_string()));
+                if let Some(msg) = docs(&variant.attrs).first() {
+                    error.insert("msg".to_string(), json!(msg));
+                }
+                Value::Object(error)
+            })
+            .collect()
+    }
+
+    fn types(&self) -> Vec<Value> {
+        self.accounts
+            .iter()
+            .chain(self.ix_data.iter())
+            .chain([&self.pod_option])
+            .map(|item| {
+                let mut ty = Map::new();
+                ty.insert("name".to_string(), json!(item.ident.to_string()));
+                ty.insert("docs".to_string(), json!(docs(&item.attrs)));
+                ty.insert("serialization".to_string(), json!("bytemuck"));
+                ty.insert("repr".to_string(), json!({ "kind": "c" }));
+                if !item.generics.params.is_empty() {
+                    ty.insert("generics".to_string(), json!(generic_params(item)));
+                }
+                ty.insert(
+                    "type".to_string(),
+                    json!({
+                        "kind": "struct",
+                        "fields": self.fields(item),
+                    }),
+                );
+                Value::Object(ty)
+            })
+            .collect()
+    }
+
+    fn fields(&self, item: &ItemStruct) -> Vec<Value> {
+        let Fields::Named(fields) = &item.fields else {
+            panic!("{} must have named fields", item.ident);
+        };
+        let generics: Vec<String> = item
+            .generics
+            .params
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(param) => param.ident.to_string(),
+                GenericParam::Const(param) => param.ident.to_string(),
+                GenericParam::Lifetime(_) => panic!("{} must not have lifetimes", item.ident),
+            })
+            .collect();
+
+        fields
+            .named
+            .iter()
+            .map(|field| {
+                json!({
+                    "name": field.ident.as_ref().unwrap().to_string(),
+                    "type": self.idl_type(&field.ty, &generics),
+                })
+            })
+            .collect()
+    }
+
+    /// The IDL type of a field, where `generics` are the names of the struct's generic parameters
+    fn idl_type(&self, ty: &Type, generics: &[String]) -> Value {
+        match ty {
+            Type::Array(array) => {
+                let len = match &array.len {
+                    Expr::Lit(len) => {
+                        let Lit::Int(len) = &len.lit else {
+                            panic!("array lengths must be integers");
+                        };
+                        json!(len.base10_parse::<u64>().unwrap())
+                    }
+                    Expr::Path(path) if generics.iter().any(|name| path.path.is_ident(name)) => {
+                        json!({ "generic": path.path.get_ident().unwrap().to_string() })
+                    }
+                    _ => panic!("array lengths must be literals or generic parameters"),
+                };
+                json!({ "array": [self.idl_type(&array.elem, generics), len] })
+            }
+            Type::Path(path) => {
+                let segment = path.path.segments.last().unwrap();
+                let name = segment.ident.to_string();
+                match name.as_str() {
+                    _ if generics.contains(&name) => json!({ "generic": name }),
+                    "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64"
+                    | "i128" | "bool" => json!(name),
+                    // Little-endian `Pod` wrappers, see the program's `pod` module
+                    "PodU64" => json!("u64"),
+                    "Pubkey" => json!("pubkey"),
+                    "PodOption" => json!({
+                        "defined": {
+                            "name": name,
+                            "generics": self.pod_option_args(segment, generics),
+                        }
+                    }),
+                    _ => json!({ "defined": { "name": name } }),
+                }
+            }
+            _ => panic!("unsupported field type"),
+        }
+    }
+
+    /// The `T` and `PAD` of a `PodOption`, with `PAD`'s default when it is left out
+    fn pod_option_args(&self, segment: &syn::PathSegment, generics: &[String]) -> Vec<Value> {
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            panic!("PodOption must have a type argument");
+        };
+        let Some(GenericArgument::Type(inner)) = args.args.first() else {
+            panic!("PodOption must have a type argument");
+        };
+
+        let pad = match args.args.iter().nth(1) {
+            Some(GenericArgument::Const(Expr::Lit(pad))) => pad.lit.clone(),
+            Some(_) => panic!("PodOption's PAD must be a literal"),
+            None => self
+                .pod_option
+                .generics
+                .params
+                .iter()
+                .find_map(|param| match param {
+                    GenericParam::Const(param) => match &param.default {
+                        Some(Expr::Lit(pad)) => Some(pad.lit.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .expect("PodOption's PAD has no literal default"),
+        };
+        let Lit::Int(pad) = pad else {
+            panic!("PodOption's PAD must be an integer");
+        };
+
+        vec![
+            json!({ "kind": "type", "type": self.idl_type(inner, generics) }),
+            json!({ "kind": "const", "value": pad.base10_digits() }),
+        ]
+    }
+}
+
+/// An `instruction_accounts!` invocation, see the program's `validation` module
+struct InstructionAccounts {
+    name: Ident,
+    /// The accounts in order, with their docs
+    accounts: Vec<(Ident, Vec<Attribute>)>,
+    /// Each `check`'s account and constraints
+    checks: Vec<(Ident, Punctuated<Expr, Token![,]>)>,
+}
+
+impl Parse for InstructionAccounts {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Attribute::parse_outer(input)?;
+        input.parse::<Visibility>()?;
+        input.parse::<Token![struct]>()?;
+        let name: Ident = input.parse()?;
+
+        // The program id and the arguments of `check`, which the IDL does not need
+        let args;
+        parenthesized!(args in input);
+        args.step(|cursor| {
+            let mut rest = *cursor;
+            while let Some((_, next)) = rest.token_tree() {
+                rest = next;
+            }
+            Ok(((), rest))
+        })?;
+
+        let body;
+        braced!(body in input);
+        let accounts = Punctuated::<(Ident, Vec<Attribute>), Token![,]>::parse_terminated_with(
+            &body,
+            |account| {
+                let attrs = Attribute::parse_outer(account)?;
+                Ok((account.parse()?, attrs))
+            },
+        )?
+        .into_iter()
+        .collect();
+
+        let mut checks = Vec::new();
+        while !input.is_empty() {
+            let check: Ident = input.parse()?;
+            if check != "check" {
+                return Err(syn::Error::new(check.span(), "expected `check`"));
+            }
+            let account: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            let constraints;
+            bracketed!(constraints in input);
+            checks.push((account, Punctuated::parse_terminated(&constraints)?));
+            input.parse::<Token![;]>()?;
+        }
+
+        Ok(Self {
+            name,
+            accounts,
+            checks,
+        })
+    }
+}
+
+impl InstructionAccounts {
+    fn idl_accounts(&self) -> Vec<Value> {
+        self.accounts
+            .iter()
+            .map(|(account, attrs)| {
+                let mut idl_account = Map::new();
+                idl_account.insert("name".to_string(), json!(account.to_string()));
+                idl_account.insert("docs".to_string(), json!(docs(attrs)));
+                for flag in ["writable", "signer"] {
+                    if self.has_constraint(account, flag) {
+                        idl_account.insert(flag.to_string(), json!(true));
+                    }
+                }
+                Value::Object(idl_account)
+            })
+            .collect()
+    }
+
+    /// Whether any `check` of `account` has the bare `constraint`, e.g. `signer`
+    fn has_constraint(&self, account: &Ident, constraint: &str) -> bool {
+        self.checks
+            .iter()
+            .filter(|(checked, _)| checked == account)
+            .flat_map(|(_, constraints)| constraints.iter())
+            .any(|expr| matches!(expr, Expr::Path(path) if path.path.is_ident(constraint)))
+    }
+}
+
+fn parse_file(path: &Path) -> Vec<Item> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    syn::parse_file(&source)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e))
+        .items
+}
+
+fn parse_dir(dir: &Path) -> Vec<Item> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+
+    paths.iter().flat_map(|path| parse_file(path)).collect()
+}
+
+fn parse_program_id(lib: &Path) -> String {
+    let source = fs::read_to_string(lib).unwrap();
+    let start = source
+        .find("declare_id!(\"")
+        .expect("program has no declare_id!")
+        + "declare_id!(\"".len();
+    let end = source[start..].find('"').unwrap() + start;
+
+    source[start..end].to_string()
+}
+
+fn find_enum(items: &[Item], name: &str) -> ItemEnum {
+    items
+        .iter()
+        .find_map(|item| match item {
+            Item::Enum(item) if item.ident == name => Some(item.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("enum {} not found", name))
+}
+
+fn instruction_accounts(items: &[Item]) -> Vec<InstructionAccounts> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Macro(item) if item.mac.path.is_ident("instruction_accounts") => Some(
+                item.mac
+                    .parse_body()
+                    .unwrap_or_else(|e| panic!("failed to parse instruction_accounts!: {}", e)),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The generic parameters of a struct, as the IDL declares them
+fn generic_params(item: &ItemStruct) -> Vec<Value> {
+    item.generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => json!({ "kind": "type", "name": param.ident.to_string() }),
+            GenericParam::Const(param) => {
+                let Type::Path(ty) = &param.ty else {
+                    panic!("{} has an unsupported const parameter", item.ident);
+                };
+                json!({
+                    "kind": "const",
+                    "name": param.ident.to_string(),
+                    "type": ty.path.get_ident().unwrap().to_string(),
+                })
+            }
+            GenericParam::Lifetime(_) => panic!("{} must not have lifetimes", item.ident),
+        })
+        .collect()
+}
+
+fn structs(items: &[Item]) -> Vec<ItemStruct> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns every variant with its discriminant, following Rust's implicit numbering
+fn enum_values(item: &ItemEnum) -> Vec<(&syn::Variant, u64)> {
+    let mut next = 0;
+    item.variants
+        .iter()
+        .map(|variant| {
+            if let Some((_, Expr::Lit(expr))) = &variant.discriminant {
+                if let Lit::Int(value) = &expr.lit {
+                    next = value.base10_parse().unwrap();
+                }
+            }
+            let value = next;
+            next += 1;
+            (variant, value)
+        })
+        .collect()
+}
+
+fn docs(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta.require_name_value().ok()?.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(doc) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if index != 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
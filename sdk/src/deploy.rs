@@ -0,0 +1,86 @@
+use anyhow::{anyhow, Result};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    hash::{hash, Hash},
+    pubkey::Pubkey,
+};
+
+/// The deployed bytes of an upgradeable program, with its programdata header decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramData {
+    pub address: Pubkey,
+    /// The slot the program was last deployed or upgraded at
+    pub slot: u64,
+    /// `None` once the program is immutable
+    pub upgrade_authority: Option<Pubkey>,
+    /// The program ELF without the loader header and trailing zero padding
+    pub elf: Vec<u8>,
+}
+
+impl ProgramData {
+    pub fn hash(&self) -> Hash {
+        hash(&self.elf)
+    }
+}
+
+/// Reads the programdata address out of an upgradeable program account
+pub fn program_data_address(program_account: &Account) -> Result<Pubkey> {
+    if program_account.owner.ne(&bpf_loader_upgradeable::id()) {
+        return Err(anyhow!(
+            "program is owned by {}, not the upgradeable loader",
+            program_account.owner
+        ));
+    }
+
+    match bincode::deserialize(&program_account.data)
+        .map_err(|e| anyhow!("failed to deserialize program account: {}", e))?
+    {
+        UpgradeableLoaderState::Program {
+            programdata_address,
+        } => Ok(programdata_address),
+        _ => Err(anyhow!("account is not an upgradeable program")),
+    }
+}
+
+/// Decodes a programdata account, stripping the loader header and the zero padding left after
+/// the ELF, which is sized for `--max-len` rather than the deployed binary
+pub fn parse_program_data(address: &Pubkey, program_data_account: &Account) -> Result<ProgramData> {
+    let header_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    if program_data_account.data.len() < header_len {
+        return Err(anyhow!(
+            "programdata account is {} bytes, shorter than its {} byte header",
+            program_data_account.data.len(),
+            header_len
+        ));
+    }
+
+    let (header, elf) = program_data_account.data.split_at(header_len);
+    match bincode::deserialize(header)
+        .map_err(|e| anyhow!("failed to deserialize programdata header: {}", e))?
+    {
+        UpgradeableLoaderState::ProgramData {
+            slot,
+            upgrade_authority_address,
+        } => Ok(ProgramData {
+            address: *address,
+            slot,
+            upgrade_authority: upgrade_authority_address,
+            elf: trim_padding(elf).to_vec(),
+        }),
+        _ => Err(anyhow!("account is not a programdata account")),
+    }
+}
+
+/// Hashes a local build artifact the same way as deployed programdata
+pub fn hash_elf(elf: &[u8]) -> Hash {
+    hash(trim_padding(elf))
+}
+
+fn trim_padding(elf: &[u8]) -> &[u8] {
+    let len = elf
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |index| index + 1);
+    &elf[..len]
+}
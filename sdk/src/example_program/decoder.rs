@@ -0,0 +1,237 @@
+use core::fmt::Debug;
+
+use anyhow::{anyhow, Result};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+use super::{
+    accounts::{config::Config, ExampleProgramDiscriminator},
+    example_program_id,
+    instructions::{
+        initialize_config::InitializeConfigIxData, update_config::UpdateConfigIxData,
+        ExampleProgramInstructions,
+    },
+    pod::load,
+    utils::{load_ix_data, DataLen},
+};
+
+/// A decoded example program instruction, with named accounts and typed data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExampleProgramInstruction {
+    InitializeConfig {
+        accounts: InitializeConfigAccounts,
+        data: InitializeConfigData,
+    },
+    UpdateConfig {
+        accounts: UpdateConfigAccounts,
+        data: UpdateConfigData,
+    },
+}
+
+/// Decoded example program instruction data, without the accounts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExampleProgramInstructionData {
+    InitializeConfig(InitializeConfigData),
+    UpdateConfig(UpdateConfigData),
+}
+
+/// A decoded example program account
+#[derive(Debug, Clone)]
+pub enum ExampleProgramAccount {
+    Config(Config),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitializeConfigAccounts {
+    pub config: Pubkey,
+    pub base: Pubkey,
+    pub admin: Pubkey,
+    pub sysvar_rent: Pubkey,
+    pub system_program: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitializeConfigData {
+    pub config_bump: u8,
+    pub fees_bps: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateConfigAccounts {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateConfigData {
+    pub new_admin: Option<Pubkey>,
+    pub new_fees_bps: Option<u64>,
+}
+
+impl ExampleProgramInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InitializeConfig { .. } => "InitializeConfig",
+            Self::UpdateConfig { .. } => "UpdateConfig",
+        }
+    }
+}
+
+impl ExampleProgramInstructionData {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InitializeConfig(_) => "InitializeConfig",
+            Self::UpdateConfig(_) => "UpdateConfig",
+        }
+    }
+}
+
+/// Decodes an instruction targeting the example program
+pub fn decode_instruction(ix: &Instruction) -> Result<ExampleProgramInstruction> {
+    decode_instruction_with_program_id(&example_program_id(), ix)
+}
+
+/// Decodes an instruction targeting the example program deployed at `program_id`
+pub fn decode_instruction_with_program_id(
+    program_id: &Pubkey,
+    ix: &Instruction,
+) -> Result<ExampleProgramInstruction> {
+    if ix.program_id.ne(program_id) {
+        return Err(anyhow!(
+            "instruction is for program {}, not the example program {}",
+            ix.program_id,
+            program_id
+        ));
+    }
+
+    let accounts: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    decode_instruction_parts(&accounts, &ix.data)
+}
+
+/// Decodes the account keys and data of an example program instruction, as found in a compiled
+/// transaction message
+pub fn decode_instruction_parts(
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Result<ExampleProgramInstruction> {
+    match decode_instruction_data(data)? {
+        ExampleProgramInstructionData::InitializeConfig(data) => {
+            let [config, base, admin, sysvar_rent, system_program] =
+                expect_accounts("InitializeConfig", accounts)?;
+
+            Ok(ExampleProgramInstruction::InitializeConfig {
+                accounts: InitializeConfigAccounts {
+                    config,
+                    base,
+                    admin,
+                    sysvar_rent,
+                    system_program,
+                },
+                data,
+            })
+        }
+        ExampleProgramInstructionData::UpdateConfig(data) => {
+            let [config, admin] = expect_accounts("UpdateConfig", accounts)?;
+
+            Ok(ExampleProgramInstruction::UpdateConfig {
+                accounts: UpdateConfigAccounts { config, admin },
+                data,
+            })
+        }
+    }
+}
+
+/// Decodes example program instruction data on its own, without the accounts
+pub fn decode_instruction_data(data: &[u8]) -> Result<ExampleProgramInstructionData> {
+    let discriminator = data
+        .first()
+        .ok_or_else(|| anyhow!("instruction data is empty"))?;
+    let discriminator = ExampleProgramInstructions::try_from(discriminator)
+        .map_err(|_| anyhow!("unknown instruction discriminator: {}", discriminator))?;
+
+    match discriminator {
+        ExampleProgramInstructions::InitializeConfig => {
+            let ix_data = read_ix_data(
+                "InitializeConfig",
+                data,
+                load_ix_data::<InitializeConfigIxData>,
+            )?;
+
+            Ok(ExampleProgramInstructionData::InitializeConfig(
+                InitializeConfigData {
+                    config_bump: ix_data.config_bump,
+                    fees_bps: ix_data.fees_bps.get(),
+                },
+            ))
+        }
+        ExampleProgramInstructions::UpdateConfig => {
+            let ix_data = read_ix_data("UpdateConfig", data, UpdateConfigIxData::load)?;
+
+            Ok(ExampleProgramInstructionData::UpdateConfig(
+                UpdateConfigData {
+                    new_admin: ix_data.new_admin.get().map(Pubkey::new_from_array),
+                    new_fees_bps: ix_data.new_fees_bps.get().map(u64::from),
+                },
+            ))
+        }
+    }
+}
+
+/// Decodes the data of an account owned by the example program
+pub fn decode_account(data: &[u8]) -> Result<ExampleProgramAccount> {
+    let discriminator = data
+        .first()
+        .ok_or_else(|| anyhow!("account data is empty"))?;
+
+    match *discriminator {
+        d if d == ExampleProgramDiscriminator::Config as u8 => {
+            check_len::<Config>("Config", "account data", data)?;
+
+            let config = load::<Config>(data).map_err(|e| anyhow!("Config: {:?}", e))?;
+            Ok(ExampleProgramAccount::Config(*config))
+        }
+        d => Err(anyhow!("unknown account discriminator: {}", d)),
+    }
+}
+
+fn expect_accounts<const N: usize>(name: &str, accounts: &[Pubkey]) -> Result<[Pubkey; N]> {
+    accounts.try_into().map_err(|_| {
+        anyhow!(
+            "{} expects {} accounts, got {}",
+            name,
+            N,
+            accounts.len()
+        )
+    })
+}
+
+fn check_len<T: DataLen>(name: &str, kind: &str, data: &[u8]) -> Result<()> {
+    if data.len() != T::LEN {
+        return Err(anyhow!(
+            "{} expects {} bytes of {}, got {}",
+            name,
+            T::LEN,
+            kind,
+            data.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Instruction data is only read through the program's own loader, so the decoder accepts exactly
+/// what the program does: the length and every option tag are checked before the bytes are used
+fn read_ix_data<T: DataLen + Copy, E: Debug>(
+    name: &str,
+    data: &[u8],
+    load: impl FnOnce(&[u8]) -> core::result::Result<&T, E>,
+) -> Result<T> {
+    load(data).copied().map_err(|e| {
+        anyhow!(
+            "{} expects {} bytes of instruction data with option tags of 0 or 1, got {}: {:?}",
+            name,
+            T::LEN,
+            data.len(),
+            e
+        )
+    })
+}
@@ -0,0 +1,7 @@
+/// The example program IDL, committed as `sdk/idl/example_program.json`
+pub const IDL_JSON: &str = include_str!("../../idl/example_program.json");
+
+/// The IDL generated by `build.rs` from the program sources, which `IDL_JSON` must match
+#[cfg(feature = "idl")]
+pub const GENERATED_IDL_JSON: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/example_program_idl.json"));
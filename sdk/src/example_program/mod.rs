@@ -1,18 +1,28 @@
-use solana_sdk::{
+use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    rent::sysvar as sysvar_rent,
     system_program,
+    sysvar::rent as sysvar_rent,
 };
 use anyhow::Result;
 
+use self::{accounts::config::Config, pda::Pda};
+
 pub mod accounts {
+    pub use pinocchio_template_example_program::accounts::ExampleProgramDiscriminator;
+
     pub mod config {
         pub use pinocchio_template_example_program::accounts::config::Config;
     }
 }
 
+pub mod errors {
+    pub use pinocchio_template_example_program::errors::ExampleProgramError;
+}
+
 pub mod instructions {
+    pub use pinocchio_template_example_program::instructions::ExampleProgramInstructions;
+
     pub mod initialize_config {
         pub use pinocchio_template_example_program::instructions::initialize_config::InitializeConfigIxData;
     }
@@ -22,10 +32,21 @@ pub mod instructions {
     }
 }
 
+pub mod pda {
+    pub use pinocchio_template_example_program::pda::*;
+}
+
+pub mod pod {
+    pub use pinocchio_template_example_program::pod::*;
+}
+
 pub mod utils {
     pub use pinocchio_template_example_program::utils::*;
 }
 
+pub mod decoder;
+pub mod idl;
+
 // ----------------------- PROGRAM ID -----------------------
 pub fn example_program_id() -> Pubkey {
     pinocchio_template_example_program::id().into()
@@ -33,15 +54,34 @@ pub fn example_program_id() -> Pubkey {
 
 // ----------------------- CONFIG -----------------------
 pub fn config_address(base: &Pubkey) -> (Pubkey, u8) {
-    let seeds = [accounts::config::Config::SEED, &base.to_bytes()];
-    Pubkey::find_program_address(&seeds, &example_program_id())
+    config_address_with_program_id(&example_program_id(), base)
+}
+
+/// Derives the config address for the program deployed at `program_id`
+pub fn config_address_with_program_id(program_id: &Pubkey, base: &Pubkey) -> (Pubkey, u8) {
+    let (config, bump) = Config::find(&base.to_bytes(), &program_id.to_bytes());
+    (Pubkey::new_from_array(config), bump)
+}
+
+/// Re-derives the config address from a known bump, failing if `bump` does not produce a PDA
+pub fn config_address_with_bump(base: &Pubkey, bump: u8) -> Result<Pubkey> {
+    config_address_with_bump_and_program_id(&example_program_id(), base, bump)
+}
+
+pub fn config_address_with_bump_and_program_id(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey> {
+    Config::create_with_bump(&base.to_bytes(), bump, &program_id.to_bytes())
+        .map(Pubkey::new_from_array)
+        .map_err(|e| anyhow::anyhow!("invalid config bump {}: {:?}", bump, e))
 }
 
 pub fn deserialize_config(data: &[u8]) -> Result<&accounts::config::Config> {
-    let config_account = unsafe { 
+    let config_account =
         pinocchio_template_example_program::utils::load_account::<accounts::config::Config>(data)
-            .map_err(|_| anyhow::anyhow!("failed to deserialize config"))?
-    };
+            .map_err(|_| anyhow::anyhow!("failed to deserialize config"))?;
     Ok(config_account)
 }
 
@@ -50,11 +90,20 @@ pub fn initialize_config_ix(
     admin: &Pubkey,
     fees_bps: u64,
 ) -> Instruction {
-    let program_id = example_program_id();
+    initialize_config_ix_with_program_id(&example_program_id(), base, admin, fees_bps)
+}
+
+/// Builds `InitializeConfig` for the program deployed at `program_id`
+pub fn initialize_config_ix_with_program_id(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    admin: &Pubkey,
+    fees_bps: u64,
+) -> Instruction {
     let sysvar_rent = sysvar_rent::id();
     let system_program = system_program::id();
 
-    let (config, config_bump) = config_address(base);
+    let (config, config_bump) = config_address_with_program_id(program_id, base);
 
     let accounts = vec![
         AccountMeta::new(config, false),
@@ -65,12 +114,10 @@ pub fn initialize_config_ix(
     ];
 
     let ix_data = instructions::initialize_config::InitializeConfigIxData::new(config_bump, fees_bps);
-    let ix_data_bytes = unsafe {
-        ix_data.to_bytes()
-    };
+    let ix_data_bytes = ix_data.to_bytes();
 
     Instruction {
-        program_id,
+        program_id: *program_id,
         accounts,
         data: ix_data_bytes.to_vec(),
     }
@@ -82,9 +129,18 @@ pub fn update_config_ix(
     new_admin: Option<Pubkey>,
     new_fees_bps: Option<u64>,
 ) -> Instruction {
-    let program_id = example_program_id();
+    update_config_ix_with_program_id(&example_program_id(), base, admin, new_admin, new_fees_bps)
+}
 
-    let (config, _) = config_address(base);
+/// Builds `UpdateConfig` for the program deployed at `program_id`
+pub fn update_config_ix_with_program_id(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    admin: &Pubkey,
+    new_admin: Option<Pubkey>,
+    new_fees_bps: Option<u64>,
+) -> Instruction {
+    let (config, _) = config_address_with_program_id(program_id, base);
 
     let accounts = vec![
         AccountMeta::new(config, false),
@@ -95,12 +151,10 @@ pub fn update_config_ix(
         new_admin.map(|p| p.to_bytes()),
         new_fees_bps,
     );
-    let ix_data_bytes = unsafe {
-        ix_data.to_bytes()
-    };
+    let ix_data_bytes = ix_data.to_bytes();
 
     Instruction {
-        program_id,
+        program_id: *program_id,
         accounts,
         data: ix_data_bytes.to_vec(),
     }
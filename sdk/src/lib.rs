@@ -1 +1,13 @@
-pub mod example_program;
\ No newline at end of file
+#[cfg(feature = "client")]
+pub mod deploy;
+pub mod example_program;
+#[cfg(feature = "native")]
+pub mod native;
+#[cfg(feature = "client")]
+pub mod offline;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "token")]
+pub mod token;
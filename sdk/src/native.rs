@@ -0,0 +1,384 @@
+//! Runs the example program's `process_instruction` on the host, without `cargo build-sbf` or a
+//! `ProgramTest` bank.
+//!
+//! Accounts are serialized into the same input buffer the runtime hands to the SBF entrypoint
+//! and deserialized with pinocchio's own entrypoint code, so the program sees real
+//! `AccountInfo`s. Syscalls and the system program CPI fall back to host implementations inside
+//! the program.
+
+use std::collections::HashMap;
+
+use pinocchio::{
+    account_info::MAX_PERMITTED_DATA_INCREASE, entrypoint::deserialize, MAX_TX_ACCOUNTS,
+};
+use pinocchio_template_example_program::utils::with_host_create_account;
+use solana_program::{
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program, sysvar,
+};
+
+pub use pinocchio::account_info::AccountInfo;
+pub use pinocchio_template_example_program::{
+    instructions::{
+        initialize_config::process_initilaize_config, update_config::process_update_config,
+    },
+    process_instruction,
+};
+
+const NATIVE_LOADER_ID: Pubkey =
+    solana_program::pubkey!("NativeLoader1111111111111111111111111111111");
+
+const NON_DUP_MARKER: u8 = u8::MAX;
+/// Dup flag, signer, writable, executable and the original data length
+const ACCOUNT_FLAGS_LEN: usize = 8;
+const OWNER_OFFSET: usize = ACCOUNT_FLAGS_LEN + 32;
+const LAMPORTS_OFFSET: usize = OWNER_OFFSET + 32;
+const DATA_LEN_OFFSET: usize = LAMPORTS_OFFSET + 8;
+const DATA_OFFSET: usize = DATA_LEN_OFFSET + 8;
+
+/// The state of an account, without the per-instruction signer and writable flags
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NativeAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+impl NativeAccount {
+    pub fn new(lamports: u64, data: Vec<u8>, owner: Pubkey) -> Self {
+        Self {
+            lamports,
+            data,
+            owner,
+            executable: false,
+        }
+    }
+}
+
+/// A serialized program input, laid out like the runtime's aligned loader input
+pub struct NativeInput {
+    /// `u64` backed so account data is 8 byte aligned, as it is on chain
+    buffer: Vec<u64>,
+    /// Offset of each account in the buffer, pointing at the first occurrence for duplicates
+    account_offsets: Vec<usize>,
+}
+
+impl NativeInput {
+    /// Serializes `accounts`, where `accounts[i]` is the state of `metas[i]`
+    pub fn new(
+        program_id: &Pubkey,
+        metas: &[AccountMeta],
+        accounts: &[NativeAccount],
+        data: &[u8],
+    ) -> Self {
+        assert_eq!(
+            metas.len(),
+            accounts.len(),
+            "every account meta needs a state"
+        );
+
+        let mut bytes = Vec::new();
+        let mut account_offsets = Vec::with_capacity(metas.len());
+        bytes.extend_from_slice(&(metas.len() as u64).to_le_bytes());
+
+        for (index, (meta, account)) in metas.iter().zip(accounts.iter()).enumerate() {
+            if let Some(original) = metas[..index].iter().position(|m| m.pubkey == meta.pubkey) {
+                // The runtime only passes the index of the first occurrence for duplicates
+                bytes.push(original as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                account_offsets.push(account_offsets[original]);
+                continue;
+            }
+
+            // Duplicates share one entry, so it carries the union of their flags
+            let (is_signer, is_writable) = metas[index..]
+                .iter()
+                .filter(|m| m.pubkey == meta.pubkey)
+                .fold((false, false), |(signer, writable), m| {
+                    (signer || m.is_signer, writable || m.is_writable)
+                });
+
+            account_offsets.push(bytes.len());
+            bytes.extend_from_slice(&[
+                NON_DUP_MARKER,
+                is_signer as u8,
+                is_writable as u8,
+                account.executable as u8,
+                0,
+                0,
+                0,
+                0,
+            ]);
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            // Rent epoch
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(program_id.as_ref());
+
+        let mut buffer = vec![0u64; bytes.len().div_ceil(8)];
+        copy_to_words(&mut buffer, &bytes);
+
+        Self {
+            buffer,
+            account_offsets,
+        }
+    }
+
+    /// Deserializes the input the way the program entrypoint does and passes the account infos
+    /// to `f`, which can call `process_instruction` or any processor and account method
+    pub fn invoke<R>(&mut self, f: impl FnOnce(&[u8; 32], &[AccountInfo], &[u8]) -> R) -> R {
+        const UNINIT: core::mem::MaybeUninit<AccountInfo> = core::mem::MaybeUninit::uninit();
+        let mut accounts = [UNINIT; MAX_TX_ACCOUNTS];
+
+        // SAFETY: the buffer was laid out by `new` in the runtime's input format and is
+        // exclusively borrowed for the lifetime of the account infos
+        unsafe {
+            let (program_id, count, data) =
+                deserialize::<MAX_TX_ACCOUNTS>(self.buffer.as_mut_ptr() as *mut u8, &mut accounts);
+            let accounts =
+                core::slice::from_raw_parts(accounts.as_ptr() as *const AccountInfo, count);
+
+            f(program_id, accounts, data)
+        }
+    }
+
+    /// Reads back the current state of the account at `index`
+    pub fn account(&self, index: usize) -> NativeAccount {
+        let bytes = self.bytes();
+        let offset = self.account_offsets[index];
+        let read_u64 = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+
+        let data_len = read_u64(offset + DATA_LEN_OFFSET) as usize;
+        let data_start = offset + DATA_OFFSET;
+
+        NativeAccount {
+            lamports: read_u64(offset + LAMPORTS_OFFSET),
+            data: bytes[data_start..data_start + data_len].to_vec(),
+            owner: Pubkey::new_from_array(bytes[offset + OWNER_OFFSET..][..32].try_into().unwrap()),
+            executable: bytes[offset + 3] != 0,
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: any initialized `u64` slice is a valid byte slice of 8 times the length
+        unsafe {
+            core::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.buffer.len() * 8)
+        }
+    }
+}
+
+fn copy_to_words(buffer: &mut [u64], bytes: &[u8]) {
+    for (word, chunk) in buffer.iter_mut().zip(bytes.chunks(8)) {
+        let mut le = [0u8; 8];
+        le[..chunk.len()].copy_from_slice(chunk);
+        *word = u64::from_ne_bytes(le);
+    }
+}
+
+/// Runs `ix` through the example program's `process_instruction`, where `accounts[i]` is the
+/// state of `ix.accounts[i]`. Like the runtime, account changes are only kept on success.
+pub fn process_native_instruction(
+    ix: &Instruction,
+    accounts: &mut [NativeAccount],
+) -> Result<(), ProgramError> {
+    let mut input = NativeInput::new(&ix.program_id, &ix.accounts, accounts, &ix.data);
+
+    input
+        .invoke(|program_id, account_infos, data| {
+            process_instruction(program_id, account_infos, data)
+        })
+        .map_err(|error| ProgramError::from(u64::from(error)))?;
+
+    for (index, account) in accounts.iter_mut().enumerate() {
+        *account = input.account(index);
+    }
+
+    Ok(())
+}
+
+/// The example program as a `solana-program` entrypoint, to register it as a `ProgramTest`
+/// builtin with `processor!`. The system program CPI is invoked for real through the
+/// `ProgramTest` syscall stubs, so the bank checks it like it checks the SBF build.
+pub fn process_instruction_builtin(
+    program_id: &Pubkey,
+    accounts: &[solana_program::account_info::AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: info.is_signer,
+            is_writable: info.is_writable,
+        })
+        .collect();
+    let states: Vec<NativeAccount> = accounts
+        .iter()
+        .map(|info| NativeAccount {
+            lamports: info.lamports(),
+            data: info.data.borrow().to_vec(),
+            owner: *info.owner,
+            executable: info.executable,
+        })
+        .collect();
+    let mut input = NativeInput::new(program_id, &metas, &states, data);
+
+    with_host_create_account(invoke_create_account, || {
+        input.invoke(|program_id, account_infos, data| {
+            process_instruction(program_id, account_infos, data)
+        })
+    })
+    .map_err(|error| ProgramError::from(u64::from(error)))?;
+
+    for (index, info) in accounts.iter().enumerate() {
+        if !info.is_writable {
+            continue;
+        }
+
+        let account = input.account(index);
+        **info.try_borrow_mut_lamports()? = account.lamports;
+        info.realloc(account.data.len(), false)?;
+        info.try_borrow_mut_data()?.copy_from_slice(&account.data);
+        if *info.owner != account.owner {
+            info.assign(&account.owner);
+        }
+    }
+
+    Ok(())
+}
+
+/// Invokes the system program's `CreateAccount` through `solana-program`, with account infos
+/// that point into the program's own input so the results land where the program reads them
+fn invoke_create_account(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    space: u64,
+    owner: &[u8; 32],
+    lamports: u64,
+    signer_seeds: &[&[u8]],
+) -> pinocchio::ProgramResult {
+    let ix = system_instruction::create_account(
+        &Pubkey::new_from_array(*from.key()),
+        &Pubkey::new_from_array(*to.key()),
+        lamports,
+        space,
+        &Pubkey::new_from_array(*owner),
+    );
+
+    // SAFETY: the infos only live for the CPI, while the program holds no other borrows
+    let account_infos = unsafe { [solana_account_info(from), solana_account_info(to)] };
+
+    invoke_signed(&ix, &account_infos, &[signer_seeds])
+        .map_err(|error| pinocchio::program_error::ProgramError::from(u64::from(error)))
+}
+
+/// # Safety
+///
+/// No other borrow of `info`'s lamports or data may be alive while the returned info is used.
+unsafe fn solana_account_info(info: &AccountInfo) -> solana_program::account_info::AccountInfo<'_> {
+    solana_program::account_info::AccountInfo::new(
+        &*(info.key() as *const [u8; 32] as *const Pubkey),
+        info.is_signer(),
+        info.is_writable(),
+        info.borrow_mut_lamports_unchecked(),
+        info.borrow_mut_data_unchecked(),
+        &*(info.owner() as *const [u8; 32] as *const Pubkey),
+        info.executable(),
+        u64::MAX,
+    )
+}
+
+/// A minimal account store to run instruction sequences natively, with the rent sysvar and
+/// the system program preloaded
+#[derive(Debug, Clone)]
+pub struct NativeContext {
+    pub accounts: HashMap<Pubkey, NativeAccount>,
+}
+
+impl Default for NativeContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeContext {
+    pub fn new() -> Self {
+        let rent = Rent::default();
+        let mut rent_data = Vec::with_capacity(17);
+        rent_data.extend_from_slice(&rent.lamports_per_byte_year.to_le_bytes());
+        rent_data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+        rent_data.push(rent.burn_percent);
+
+        let mut accounts = HashMap::new();
+        accounts.insert(
+            sysvar::rent::id(),
+            NativeAccount::new(1, rent_data, sysvar::id()),
+        );
+        accounts.insert(
+            system_program::id(),
+            NativeAccount {
+                executable: true,
+                ..NativeAccount::new(1, vec![], NATIVE_LOADER_ID)
+            },
+        );
+
+        Self { accounts }
+    }
+
+    /// Adds a system account holding `sol`, e.g. an admin that pays for the config
+    pub fn fund(&mut self, address: &Pubkey, sol: u64) {
+        self.accounts
+            .entry(*address)
+            .or_insert_with(|| NativeAccount::new(0, vec![], system_program::id()))
+            .lamports += sol * LAMPORTS_PER_SOL;
+    }
+
+    pub fn set_account(&mut self, address: &Pubkey, account: NativeAccount) {
+        self.accounts.insert(*address, account);
+    }
+
+    pub fn get_account(&self, address: &Pubkey) -> Option<&NativeAccount> {
+        self.accounts.get(address)
+    }
+
+    /// Processes `ix`, treating accounts the store has never seen as empty system accounts.
+    /// Signer flags are taken from the account metas, there are no signatures on the host.
+    pub fn process_instruction(&mut self, ix: &Instruction) -> Result<(), ProgramError> {
+        let mut accounts: Vec<NativeAccount> = ix
+            .accounts
+            .iter()
+            .map(|meta| {
+                self.accounts
+                    .get(&meta.pubkey)
+                    .cloned()
+                    .unwrap_or_else(|| NativeAccount::new(0, vec![], system_program::id()))
+            })
+            .collect();
+
+        process_native_instruction(ix, &mut accounts)?;
+
+        for (meta, account) in ix.accounts.iter().zip(accounts) {
+            if meta.is_writable {
+                self.accounts.insert(meta.pubkey, account);
+            }
+        }
+
+        Ok(())
+    }
+}
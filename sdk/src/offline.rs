@@ -0,0 +1,229 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use solana_sanitize::Sanitize;
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    nonce::state::{State as NonceState, Versions as NonceVersions},
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::{uses_durable_nonce, Transaction},
+};
+
+/// How a serialized transaction is handed between machines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionEncoding {
+    Base58,
+    Base64,
+}
+
+impl FromStr for TransactionEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "base58" => Ok(Self::Base58),
+            "base64" => Ok(Self::Base64),
+            _ => Err(anyhow!("unknown transaction encoding: {}", s)),
+        }
+    }
+}
+
+/// Reads the durable nonce stored in a nonce account
+pub fn nonce_hash(nonce_account: &Account) -> Result<Hash> {
+    if nonce_account.owner.ne(&solana_sdk::system_program::id()) {
+        return Err(anyhow!("nonce account is not owned by the system program"));
+    }
+
+    let versions: NonceVersions = bincode::deserialize(&nonce_account.data)
+        .map_err(|e| anyhow!("failed to deserialize nonce account: {}", e))?;
+
+    match versions.state() {
+        NonceState::Initialized(data) => Ok(data.blockhash()),
+        NonceState::Uninitialized => Err(anyhow!("nonce account is not initialized")),
+    }
+}
+
+/// Builds an unsigned transaction that advances `nonce_account` before `instructions`, so it
+/// stays valid until the nonce is used instead of expiring with a recent blockhash
+pub fn build_nonce_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce_authority: &Pubkey,
+    nonce_hash: Hash,
+) -> Transaction {
+    let mut message = Message::new_with_nonce(
+        instructions.to_vec(),
+        Some(fee_payer),
+        nonce_account,
+        nonce_authority,
+    );
+    message.recent_blockhash = nonce_hash;
+
+    Transaction::new_unsigned(message)
+}
+
+pub fn encode_transaction(tx: &Transaction, encoding: TransactionEncoding) -> Result<String> {
+    let bytes =
+        bincode::serialize(tx).map_err(|e| anyhow!("failed to serialize transaction: {}", e))?;
+
+    Ok(match encoding {
+        TransactionEncoding::Base58 => bs58::encode(bytes).into_string(),
+        TransactionEncoding::Base64 => BASE64.encode(bytes),
+    })
+}
+
+pub fn decode_transaction(encoded: &str, encoding: TransactionEncoding) -> Result<Transaction> {
+    let bytes = match encoding {
+        TransactionEncoding::Base58 => bs58::decode(encoded.trim())
+            .into_vec()
+            .map_err(|e| anyhow!("invalid base58 transaction: {}", e))?,
+        TransactionEncoding::Base64 => BASE64
+            .decode(encoded.trim())
+            .map_err(|e| anyhow!("invalid base64 transaction: {}", e))?,
+    };
+
+    let tx: Transaction = bincode::deserialize(&bytes)
+        .map_err(|e| anyhow!("failed to deserialize transaction: {}", e))?;
+    // The signers and instructions index into the account keys
+    tx.sanitize()
+        .map_err(|e| anyhow!("invalid transaction: {}", e))?;
+
+    Ok(tx)
+}
+
+/// What a transaction does, for a signer to review before signing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSummary {
+    pub fee_payer: Pubkey,
+    /// The nonce account and nonce authority, when the transaction advances a durable nonce
+    pub nonce: Option<(Pubkey, Pubkey)>,
+    pub recent_blockhash: Hash,
+    /// Every instruction after the nonce advance, with signer and writable flags from the message
+    pub instructions: Vec<Instruction>,
+}
+
+/// Decompiles a transaction's message into its fee payer, durable nonce and instructions
+pub fn summarize_transaction(tx: &Transaction) -> Result<TransactionSummary> {
+    tx.sanitize()
+        .map_err(|e| anyhow!("invalid transaction: {}", e))?;
+    let message = &tx.message;
+
+    // `AdvanceNonceAccount` takes the nonce account, the recent blockhashes sysvar and the
+    // nonce authority
+    let nonce = uses_durable_nonce(tx)
+        .map(|ix| match ix.accounts[..] {
+            [nonce, _, authority, ..] => Ok((
+                message.account_keys[nonce as usize],
+                message.account_keys[authority as usize],
+            )),
+            _ => Err(anyhow!("nonce advance instruction is missing accounts")),
+        })
+        .transpose()?;
+
+    let instructions = message
+        .instructions
+        .iter()
+        .skip(nonce.map_or(0, |_| 1))
+        .map(|ix| Instruction {
+            program_id: message.account_keys[ix.program_id_index as usize],
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta {
+                    pubkey: message.account_keys[index as usize],
+                    is_signer: message.is_signer(index as usize),
+                    is_writable: message.is_maybe_writable(index as usize, None),
+                })
+                .collect(),
+            data: ix.data.clone(),
+        })
+        .collect();
+
+    Ok(TransactionSummary {
+        fee_payer: message.account_keys[0],
+        nonce,
+        recent_blockhash: message.recent_blockhash,
+        instructions,
+    })
+}
+
+/// Signs the transaction message without touching the transaction, for signers that never see
+/// the final transaction
+pub fn sign_detached(tx: &Transaction, signer: &dyn Signer) -> Result<(Pubkey, Signature)> {
+    let pubkey = signer.try_pubkey()?;
+    required_signer_position(tx, &pubkey)?;
+
+    let signature = signer.try_sign_message(&tx.message_data())?;
+
+    Ok((pubkey, signature))
+}
+
+/// Verifies a detached signature and places it in the transaction
+pub fn add_detached_signature(
+    tx: &mut Transaction,
+    pubkey: &Pubkey,
+    signature: &Signature,
+) -> Result<()> {
+    let position = required_signer_position(tx, pubkey)?;
+
+    if !signature.verify(pubkey.as_ref(), &tx.message_data()) {
+        return Err(anyhow!(
+            "signature for {} does not match the transaction",
+            pubkey
+        ));
+    }
+
+    let slot = tx
+        .signatures
+        .get_mut(position)
+        .ok_or_else(|| anyhow!("transaction has no signature slot for {}", pubkey))?;
+    *slot = *signature;
+
+    Ok(())
+}
+
+/// Returns the first required signer that has not signed yet, or has no signature slot at all
+pub fn missing_signer(tx: &Transaction) -> Option<Pubkey> {
+    tx.message
+        .signer_keys()
+        .into_iter()
+        .enumerate()
+        .find(|(position, _)| {
+            tx.signatures
+                .get(*position)
+                .is_none_or(|signature| *signature == Signature::default())
+        })
+        .map(|(_, pubkey)| *pubkey)
+}
+
+/// Formats a detached signature as `<pubkey>=<signature>`, the same format `solana --signer` takes
+pub fn format_detached_signature(pubkey: &Pubkey, signature: &Signature) -> String {
+    format!("{}={}", pubkey, signature)
+}
+
+pub fn parse_detached_signature(value: &str) -> Result<(Pubkey, Signature)> {
+    let (pubkey, signature) = value
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected <pubkey>=<signature>, got {}", value))?;
+
+    let pubkey = Pubkey::from_str(pubkey).map_err(|e| anyhow!("invalid pubkey: {}", e))?;
+    let signature =
+        Signature::from_str(signature).map_err(|e| anyhow!("invalid signature: {}", e))?;
+
+    Ok((pubkey, signature))
+}
+
+fn required_signer_position(tx: &Transaction, pubkey: &Pubkey) -> Result<usize> {
+    // `signer_keys` is clamped to the account keys for transactions that were not sanitized
+    tx.message
+        .signer_keys()
+        .into_iter()
+        .position(|key| key.eq(pubkey))
+        .ok_or_else(|| anyhow!("{} is not a required signer of the transaction", pubkey))
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
+
+use crate::{
+    deploy::{parse_program_data, program_data_address, ProgramData},
+    example_program::{
+        accounts::config::Config, config_address_with_program_id, deserialize_config,
+        example_program_id,
+    },
+    offline::nonce_hash,
+};
+
+/// Fetches the config derived from `base`
+pub fn get_config(client: &RpcClient, base: &Pubkey) -> Result<Config> {
+    get_config_with_program_id(client, &example_program_id(), base)
+}
+
+/// Fetches the config derived from `base` for the program deployed at `program_id`
+pub fn get_config_with_program_id(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    base: &Pubkey,
+) -> Result<Config> {
+    let (config, _) = config_address_with_program_id(program_id, base);
+    let account = client.get_account(&config)?;
+
+    Ok(*deserialize_config(&account.data)?)
+}
+
+/// Fetches the durable nonce stored in `nonce_account`
+pub fn get_nonce_hash(client: &RpcClient, nonce_account: &Pubkey) -> Result<Hash> {
+    let account = client.get_account(nonce_account)?;
+
+    nonce_hash(&account)
+}
+
+/// Fetches the programdata of the upgradeable program at `program_id`
+pub fn get_program_data(client: &RpcClient, program_id: &Pubkey) -> Result<ProgramData> {
+    let program_account = client.get_account(program_id)?;
+    let address = program_data_address(&program_account)?;
+    let program_data_account = client.get_account(&address)?;
+
+    parse_program_data(&address, &program_data_account)
+}
@@ -0,0 +1,27 @@
+use solana_program_test::{processor, ProgramTest};
+
+use crate::{example_program::example_program_id, native::process_instruction_builtin};
+
+/// A `ProgramTest` with the example program loaded from `SBF_OUT_DIR`
+pub fn example_program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "pinocchio_template_example_program",
+        example_program_id(),
+        None,
+    );
+    program_test.prefer_bpf(true);
+
+    program_test
+}
+
+/// A `ProgramTest` with the example program registered as a native builtin, no SBF build needed
+pub fn example_program_test_native() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "pinocchio_template_example_program",
+        example_program_id(),
+        processor!(process_instruction_builtin),
+    );
+    program_test.prefer_bpf(false);
+
+    program_test
+}
@@ -0,0 +1,21 @@
+use anyhow::{anyhow, Result};
+use spl_token_2022::{
+    extension::StateWithExtensionsOwned,
+    state::{Account, Mint},
+};
+
+pub use spl_associated_token_account;
+pub use spl_token;
+pub use spl_token_2022;
+
+/// Unpacks a mint owned by either token program, with its extensions
+pub fn unpack_mint(data: Vec<u8>) -> Result<StateWithExtensionsOwned<Mint>> {
+    StateWithExtensionsOwned::<Mint>::unpack(data)
+        .map_err(|e| anyhow!("failed to unpack mint: {}", e))
+}
+
+/// Unpacks a token account owned by either token program, with its extensions
+pub fn unpack_token_account(data: Vec<u8>) -> Result<StateWithExtensionsOwned<Account>> {
+    StateWithExtensionsOwned::<Account>::unpack(data)
+        .map_err(|e| anyhow!("failed to unpack token account: {}", e))
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+#[path = "../../programs/example_program/src/golden.rs"]
+mod layouts;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pinocchio_template_sdk::example_program::{
+        config_address,
+        decoder::{
+            decode_account, decode_instruction_data, ExampleProgramAccount,
+            ExampleProgramInstructionData, InitializeConfigData, UpdateConfigData,
+        },
+        deserialize_config, initialize_config_ix, update_config_ix,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    use super::layouts::{layout, Vector};
+
+    fn address(vector: &Vector, field: &str) -> Pubkey {
+        Pubkey::from_str(vector.address(field)).unwrap()
+    }
+
+    fn option_address(vector: &Vector, field: &str) -> Option<Pubkey> {
+        vector
+            .option_address(field)
+            .map(|address| Pubkey::from_str(address).unwrap())
+    }
+
+    #[test]
+    fn test_config_golden_decode() {
+        for vector in layout("Config").vectors {
+            let expected = (
+                vector.u8("bump"),
+                address(&vector, "base"),
+                address(&vector, "admin"),
+                vector.u64("fees_bps"),
+            );
+
+            let ExampleProgramAccount::Config(config) = decode_account(&vector.bytes).unwrap();
+            let decoded = (
+                config.bump(),
+                Pubkey::new_from_array(*config.base()),
+                Pubkey::new_from_array(*config.admin()),
+                config.fees_bps(),
+            );
+            assert_eq!(
+                expected, decoded,
+                "Config {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+
+            let config = deserialize_config(&vector.bytes).unwrap();
+            let deserialized = (
+                config.bump(),
+                Pubkey::new_from_array(*config.base()),
+                Pubkey::new_from_array(*config.admin()),
+                config.fees_bps(),
+            );
+            assert_eq!(
+                expected, deserialized,
+                "Config {:?} deserialized differently, golden left, actual right",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_config_ix_data_golden_decode() {
+        for vector in layout("InitializeConfigIxData").vectors {
+            assert_eq!(
+                ExampleProgramInstructionData::InitializeConfig(InitializeConfigData {
+                    config_bump: vector.u8("config_bump"),
+                    fees_bps: vector.u64("fees_bps"),
+                }),
+                decode_instruction_data(&vector.bytes).unwrap(),
+                "InitializeConfigIxData {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_config_ix_data_golden_encode() {
+        let layout = layout("InitializeConfigIxData");
+        let base = Pubkey::new_unique();
+        let (_, config_bump) = config_address(&base);
+
+        for vector in &layout.vectors {
+            let ix = initialize_config_ix(&base, &Pubkey::new_unique(), vector.u64("fees_bps"));
+
+            // The builder derives the bump, so the golden bytes take the derived one
+            let mut expected = vector.bytes.clone();
+            expected[layout.field("config_bump").offset] = config_bump;
+            layout.assert_bytes_eq(&vector.name, &expected, &ix.data);
+        }
+    }
+
+    #[test]
+    fn test_update_config_ix_data_golden_decode() {
+        for vector in layout("UpdateConfigIxData").vectors {
+            assert_eq!(
+                ExampleProgramInstructionData::UpdateConfig(UpdateConfigData {
+                    new_admin: option_address(&vector, "new_admin"),
+                    new_fees_bps: vector.option_u64("new_fees_bps"),
+                }),
+                decode_instruction_data(&vector.bytes).unwrap(),
+                "UpdateConfigIxData {:?} decoded differently, golden left, actual right",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_config_ix_data_golden_encode() {
+        let layout = layout("UpdateConfigIxData");
+        for vector in &layout.vectors {
+            let new_admin = option_address(vector, "new_admin");
+            let new_fees_bps = vector.option_u64("new_fees_bps");
+            let ix = update_config_ix(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                new_admin,
+                new_fees_bps,
+            );
+
+            layout.assert_bytes_eq(&vector.name, &vector.bytes, &ix.data);
+        }
+    }
+}